@@ -0,0 +1,16 @@
+//! Timing harness shared by the examples.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// Returns the average time of calling `f` `iterations` times.
+///
+/// The returned `usize` is passed through [`black_box()`], such that
+/// the work done by `f` is not optimized away.
+pub fn measure(iterations: u32, mut f: impl FnMut() -> usize) -> Duration {
+    let start = Instant::now();
+    for _ in 0..iterations {
+        black_box(f());
+    }
+    start.elapsed() / iterations
+}
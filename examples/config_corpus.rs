@@ -0,0 +1,48 @@
+//! Measures converting a corpus of 1000 small config files, using
+//! `jsonc_to_json_into()` with a reused `String`.
+//!
+//! Run with `cargo run --release --example config_corpus`
+
+mod common;
+
+use std::hint::black_box;
+
+use common::measure;
+use jsonc_to_json::jsonc_to_json_into;
+
+const ITERATIONS: u32 = 100;
+
+fn main() {
+    let corpus = (0..1_000)
+        .map(|i| {
+            format!(
+                r#"{{
+    // Config {0}
+    "name": "package-{0}",
+    "version": "1.{0}.0",
+    "tabSize": {1}, /* Spaces */
+    "include": ["src/**/*", "tests/**/*",],
+    "compilerOptions": {{
+        "strict": true,
+        "target": "es{2}",
+    }},
+}}"#,
+                i,
+                i % 8,
+                2015 + i % 8
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let mut json = String::new();
+    let elapsed = measure(ITERATIONS, || {
+        let mut len = 0;
+        for jsonc in &corpus {
+            json.clear();
+            jsonc_to_json_into(black_box(jsonc), &mut json);
+            len += json.len();
+        }
+        len
+    });
+    println!("{} files: {:?}/iter", corpus.len(), elapsed);
+}
@@ -0,0 +1,56 @@
+//! Measures the overhead of `Cow::Owned`, when only a single comment
+//! is removed from the middle of the input, compared to inputs which
+//! result in `Cow::Borrowed`.
+//!
+//! Run with `cargo run --release --example cow_overhead`
+
+mod common;
+
+use std::borrow::Cow;
+use std::hint::black_box;
+
+use common::measure;
+use jsonc_to_json::jsonc_to_json;
+
+const ITERATIONS: u32 = 1_000;
+
+fn main() {
+    let values = (0..100)
+        .map(|i| format!("\"key{}\": [{}, {}, {}]", i, i, i + 1, i + 2))
+        .collect::<Vec<_>>();
+
+    let json = format!("{{{}}}", values.join(", "));
+    let trailing_comment = format!("{} // Comment", json);
+    let (first, second) = values.split_at(values.len() / 2);
+    let middle_comment = format!(
+        "{{{}, /* Comment */ {}}}",
+        first.join(", "),
+        second.join(", ")
+    );
+
+    let inputs = [
+        ("valid json", json.as_str()),
+        ("trailing comment", trailing_comment.as_str()),
+        ("middle comment", middle_comment.as_str()),
+    ];
+
+    for (name, jsonc) in inputs {
+        let kind = match jsonc_to_json(jsonc) {
+            Cow::Borrowed(_) => "borrowed",
+            Cow::Owned(_) => "owned",
+        };
+        let elapsed = measure(ITERATIONS, || jsonc_to_json(black_box(jsonc)).len());
+        println!("{:<18} {:<8} {:?}/iter", name, kind, elapsed);
+    }
+
+    // Skipping the conversion when there are no comments
+    let elapsed = measure(ITERATIONS, || {
+        let jsonc = black_box(json.as_str());
+        if jsonc.contains('/') {
+            jsonc_to_json(jsonc).len()
+        } else {
+            jsonc.len()
+        }
+    });
+    println!("{:<18} {:<8} {:?}/iter", "pre-checked", "borrowed", elapsed);
+}
@@ -0,0 +1,46 @@
+//! Compares collecting `jsonc_to_json_iter()` into a `String`, which
+//! uses the specialized `fold()`, against calling `next()` in a loop.
+//!
+//! Run with `cargo run --release --example fold`
+
+mod common;
+
+use std::hint::black_box;
+
+use common::measure;
+use jsonc_to_json::jsonc_to_json_iter;
+
+const ITERATIONS: u32 = 1_000;
+
+fn main() {
+    let values = (0..100)
+        .map(|i| {
+            format!(
+                "\"key{}\": [{}, /* {} */ {},], // Comment\n",
+                i,
+                i,
+                i + 1,
+                i + 2
+            )
+        })
+        .collect::<String>();
+    let jsonc = format!("{{\n{}}}", values);
+
+    let elapsed = measure(ITERATIONS, || {
+        let mut json = String::new();
+        let mut iter = jsonc_to_json_iter(black_box(&jsonc));
+        #[allow(clippy::while_let_on_iterator)]
+        while let Some(part) = iter.next() {
+            json.push_str(part);
+        }
+        json.len()
+    });
+    println!("{:<8} {:?}/iter", "next", elapsed);
+
+    let elapsed = measure(ITERATIONS, || {
+        jsonc_to_json_iter(black_box(&jsonc))
+            .collect::<String>()
+            .len()
+    });
+    println!("{:<8} {:?}/iter", "fold", elapsed);
+}
@@ -0,0 +1,39 @@
+//! Measures the cost of allocating and copying into a `String`, when
+//! only line comments at the ends of lines are removed, compared to
+//! collecting the borrowed string slices into a `Vec<&str>`.
+//!
+//! Run with `cargo run --release --example line_comment_slices`
+
+mod common;
+
+use std::hint::black_box;
+
+use common::measure;
+use jsonc_to_json::{jsonc_to_json, jsonc_to_json_iter};
+
+const ITERATIONS: u32 = 1_000;
+
+fn main() {
+    for lines in [10, 100, 1_000] {
+        let body = (0..lines)
+            .map(|i| format!("  \"key{0}\": [{0}, {1}], // Comment {0}\n", i, i + 1))
+            .collect::<String>();
+        let jsonc = format!("{{\n{}  \"last\": null\n}}", body);
+
+        let parts = jsonc_to_json_iter(&jsonc).count();
+        println!("{} lines, {} bytes, {} parts", lines, jsonc.len(), parts);
+
+        let elapsed = measure(ITERATIONS, || jsonc_to_json(black_box(&jsonc)).len());
+        println!("  {:<12} {:?}/iter", "String", elapsed);
+
+        let elapsed = measure(ITERATIONS, || {
+            jsonc_to_json_iter(black_box(&jsonc))
+                .collect::<Vec<&str>>()
+                .len()
+        });
+        println!("  {:<12} {:?}/iter", "Vec<&str>", elapsed);
+
+        let elapsed = measure(ITERATIONS, || jsonc_to_json_iter(black_box(&jsonc)).count());
+        println!("  {:<12} {:?}/iter", "tokenize", elapsed);
+    }
+}
@@ -0,0 +1,45 @@
+//! Compares sequential and parallel conversion throughput on a
+//! synthetic workload of 10 000 JSONC documents.
+//!
+//! Run with `cargo run --release --example par_batch --features rayon`
+
+use std::time::Instant;
+
+use jsonc_to_json::{jsonc_to_json, jsonc_to_json_par_batch};
+
+fn main() {
+    let inputs = (0..10_000)
+        .map(|i| {
+            format!(
+                r#"{{
+    // Localization entry {0}
+    "id": {0},
+    /* Block comment */
+    "values": [{0}, {1}, {2},],
+    "label": "Label {0}",
+}}"#,
+                i,
+                i + 1,
+                i + 2,
+            )
+            .repeat(20)
+        })
+        .collect::<Vec<_>>();
+    let inputs = inputs.iter().map(String::as_str).collect::<Vec<_>>();
+
+    let start = Instant::now();
+    let sequential = inputs
+        .iter()
+        .map(|jsonc| jsonc_to_json(jsonc).into_owned())
+        .collect::<Vec<_>>();
+    let sequential_elapsed = start.elapsed();
+
+    let start = Instant::now();
+    let parallel = jsonc_to_json_par_batch(&inputs);
+    let parallel_elapsed = start.elapsed();
+
+    assert_eq!(sequential, parallel);
+
+    println!("sequential: {:?}", sequential_elapsed);
+    println!("parallel:   {:?}", parallel_elapsed);
+}
@@ -0,0 +1,43 @@
+//! Compares validating many small inputs using a reused
+//! `JsonCValidator`, against calling `validate_jsonc()`.
+//!
+//! Run with `cargo run --release --example validator`
+
+mod common;
+
+use std::hint::black_box;
+
+use common::measure;
+use jsonc_to_json::{validate_jsonc, JsonCValidator};
+
+const ITERATIONS: u32 = 1_000;
+
+fn main() {
+    let inputs = (0..100)
+        .map(|i| {
+            format!(
+                "{{\"key{}\": [{}, {{\"a\": [[{}]]}},], // Comment\n}}",
+                i,
+                i,
+                i + 1
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let elapsed = measure(ITERATIONS, || {
+        inputs
+            .iter()
+            .map(|jsonc| validate_jsonc(black_box(jsonc)).len())
+            .sum()
+    });
+    println!("{:<16} {:?}/iter", "validate_jsonc", elapsed);
+
+    let mut validator = JsonCValidator::new();
+    let elapsed = measure(ITERATIONS, || {
+        inputs
+            .iter()
+            .map(|jsonc| validator.validate(black_box(jsonc)).len())
+            .sum()
+    });
+    println!("{:<16} {:?}/iter", "JsonCValidator", elapsed);
+}
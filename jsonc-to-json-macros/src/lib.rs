@@ -0,0 +1,91 @@
+//! Compile-time conversion of [JSON with Comments] files into [JSON],
+//! using [`jsonc-to-json`].
+//!
+//! [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+//! [JSON]: https://www.json.org/json-en.html
+//! [`jsonc-to-json`]: https://docs.rs/jsonc-to-json
+
+use std::path::PathBuf;
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, LitStr};
+
+/// Same as [`include_str!`], but the included file is converted from
+/// [JSON with Comments] into [JSON] at compile time.
+///
+/// The macro evaluates to a `&'static str` of [JSON].
+///
+/// The file is located relative to the current file, i.e. the same
+/// as [`include_str!`]. Changes to the file trigger recompilation.
+///
+/// The file is validated using [`jsonc_to_json_strict()`], and invalid
+/// [JSON with Comments] results in a compile error at the invocation.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use jsonc_to_json_macros::jsonc_include;
+///
+/// // config.jsonc
+/// // {
+/// //     // Size of a tab in spaces
+/// //     "tabSize": 4,
+/// // }
+/// const CONFIG: &str = jsonc_include!("config.jsonc");
+/// ```
+///
+/// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+/// [JSON]: https://www.json.org/json-en.html
+/// [`jsonc_to_json_strict()`]: https://docs.rs/jsonc-to-json/latest/jsonc_to_json/fn.jsonc_to_json_strict.html
+#[proc_macro]
+pub fn jsonc_include(input: TokenStream) -> TokenStream {
+    let lit = parse_macro_input!(input as LitStr);
+
+    let path = resolve_path(&lit.value());
+    let display = path.display();
+
+    let jsonc = match std::fs::read_to_string(&path) {
+        Ok(jsonc) => jsonc,
+        Err(err) => {
+            let msg = format!("couldn't read `{}`: {}", display, err);
+            return syn::Error::new(lit.span(), msg).to_compile_error().into();
+        }
+    };
+
+    let json = match jsonc_to_json::jsonc_to_json_strict(&jsonc) {
+        Ok(json) => json,
+        Err(err) => {
+            let msg = format!("invalid JSONC in `{}`: {}", display, err);
+            return syn::Error::new(lit.span(), msg).to_compile_error().into();
+        }
+    };
+
+    // `include_bytes!()` makes Cargo recompile when the file changes,
+    // and it resolves relative paths relative to the invoking file
+    let path = std::fs::canonicalize(&path).unwrap_or(path);
+    let path = path.to_string_lossy();
+    quote! {
+        {
+            const _: &[u8] = ::core::include_bytes!(#path);
+            #json
+        }
+    }
+    .into()
+}
+
+/// Resolves `path` relative to the directory of the file
+/// invoking the macro, falling back to the crate root.
+fn resolve_path(path: &str) -> PathBuf {
+    let path = PathBuf::from(path);
+    if path.is_absolute() {
+        return path;
+    }
+
+    let file = proc_macro::Span::call_site().local_file();
+    let dir = match file.as_deref().and_then(|file| file.parent()) {
+        Some(dir) => dir.to_path_buf(),
+        None => PathBuf::from(std::env::var_os("CARGO_MANIFEST_DIR").unwrap_or_default()),
+    };
+    dir.join(path)
+}
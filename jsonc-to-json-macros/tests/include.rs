@@ -0,0 +1,11 @@
+use jsonc_to_json_macros::jsonc_include;
+
+const CONFIG: &str = jsonc_include!("config.jsonc");
+
+#[test]
+fn test_include() {
+    assert_eq!(
+        CONFIG,
+        "{\n    \n    \"tabSize\": 4,\n    \n    \"rulers\": [80, 100]\n}\n"
+    );
+}
@@ -0,0 +1,4 @@
+/* auto-generated by NAPI-RS */
+
+/** Converts JSON with Comments into JSON. */
+export function jsoncToJson(input: string): string
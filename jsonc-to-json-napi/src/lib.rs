@@ -0,0 +1,64 @@
+//! [Node.js] bindings for [`jsonc-to-json`], using [N-API] via [`napi-rs`].
+//!
+//! Build the native addon using the [`@napi-rs/cli`], which also
+//! generates the TypeScript declarations in `index.d.ts`:
+//!
+//! ```text
+//! npx napi build --platform --release
+//! ```
+//!
+//! Which can then be used from JavaScript:
+//!
+//! ```js
+//! const { jsoncToJson } = require("jsonc-to-json");
+//!
+//! jsoncToJson('{"arr": [1, 2,/* Comment */ 3, 4,,]}// Line Comment');
+//! // => '{"arr": [1, 2, 3, 4]}'
+//! ```
+//!
+//! This is a separate crate instead of a feature of [`jsonc-to-json`],
+//! as the addon must be built as a `cdylib`, and as the bindings generated
+//! by [`napi-rs`] require `unsafe` code, which [`jsonc-to-json`] forbids.
+//!
+//! [Node.js]: https://nodejs.org
+//! [N-API]: https://nodejs.org/api/n-api.html
+//! [`napi-rs`]: https://napi.rs
+//! [`@napi-rs/cli`]: https://www.npmjs.com/package/@napi-rs/cli
+//! [`jsonc-to-json`]: https://docs.rs/jsonc-to-json
+
+#![deny(missing_docs)]
+
+use std::borrow::Cow;
+
+use napi_derive::napi;
+
+/// Converts [JSON with Comments] into [JSON], see [`jsonc_to_json()`].
+///
+/// Exported to JavaScript as `jsoncToJson(input: string): string`.
+///
+/// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+/// [JSON]: https://www.json.org/json-en.html
+/// [`jsonc_to_json()`]: jsonc_to_json::jsonc_to_json
+#[napi(js_name = "jsoncToJson")]
+pub fn jsonc_to_json_napi(input: String) -> String {
+    match jsonc_to_json::jsonc_to_json(&input) {
+        // Reuse the allocation of `input`, when nothing was removed
+        Cow::Borrowed(json) if json.len() == input.len() => input,
+        json => json.into_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_jsonc_to_json_napi() {
+        let jsonc = "{\"arr\": [1, 2,/* Comment */ 3, 4,,]}// Line Comment";
+        assert_eq!(
+            jsonc_to_json_napi(jsonc.to_owned()),
+            "{\"arr\": [1, 2, 3, 4]}"
+        );
+        assert_eq!(jsonc_to_json_napi("[1, 2]".to_owned()), "[1, 2]");
+    }
+}
@@ -0,0 +1,407 @@
+use std::fmt;
+use std::iter::FusedIterator;
+
+use any_lexer::JsonCToken;
+
+use crate::tokens::JsonCTokens;
+use crate::JsonCToJsonIter;
+
+impl<'jsonc> JsonCToJsonIter<'jsonc> {
+    /// Calls `f` with each string slice, before yielding it.
+    ///
+    /// This is the same as [`Iterator::inspect()`], except that `f` is
+    /// called with `&str` instead of `&&str`, and the returned
+    /// [`InspectJsonCIter`] is a nameable type, e.g. for storing it
+    /// in a struct field.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use jsonc_to_json::jsonc_to_json_iter;
+    /// let jsonc = r#"{foo}/**/[1,2,3,,]"bar""#;
+    ///
+    /// let mut parts = Vec::new();
+    /// let json = jsonc_to_json_iter(jsonc)
+    ///     .inspect_parts(|part| parts.push(part.to_owned()))
+    ///     .collect::<String>();
+    ///
+    /// assert_eq!(json, "{foo}[1,2,3]\"bar\"");
+    /// assert_eq!(parts, ["{foo}", "[1,2,3", "]\"bar\""]);
+    /// ```
+    #[inline]
+    pub fn inspect_parts<F>(self, f: F) -> InspectJsonCIter<'jsonc, F>
+    where
+        F: FnMut(&str),
+    {
+        InspectJsonCIter { iter: self, f }
+    }
+
+    /// Yields `(offset, part)` pairs, where `offset` is the byte offset
+    /// of `part` in the output [JSON], i.e. not in the input.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use jsonc_to_json::jsonc_to_json_iter;
+    /// let jsonc = r#"{foo}/**/[1,2,3,,]"bar""#;
+    ///
+    /// let parts = jsonc_to_json_iter(jsonc).with_offsets().collect::<Vec<_>>();
+    /// assert_eq!(parts, [(0, "{foo}"), (5, "[1,2,3"), (11, "]\"bar\"")]);
+    /// ```
+    ///
+    /// [JSON]: https://www.json.org/json-en.html
+    #[inline]
+    pub fn with_offsets(self) -> OffsetJsonCIter<'jsonc> {
+        OffsetJsonCIter {
+            iter: self,
+            output_offset: 0,
+        }
+    }
+
+    /// Yields `(previous, part)` pairs, where `previous` is the string
+    /// slice yielded before `part`, or `None` for the first part.
+    ///
+    /// See [`jsonc_to_json_window_iter()`] for more information.
+    ///
+    /// [`jsonc_to_json_window_iter()`]: crate::jsonc_to_json_window_iter
+    #[inline]
+    pub fn with_previous(self) -> WindowJsonCIter<'jsonc> {
+        WindowJsonCIter {
+            iter: self,
+            previous: None,
+        }
+    }
+
+    /// Yields string slices until the first top-level [JSON] value
+    /// is complete, i.e. until the brackets of the first top-level
+    /// array or object are balanced.
+    ///
+    /// If the first top-level value is not an array or object, e.g. a
+    /// number, then the value is complete after the value itself.
+    ///
+    /// This allows consuming a single value, and leaving the rest of
+    /// the input for something else, see
+    /// [`TakeWhileDepthZero::as_remaining_str()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use jsonc_to_json::jsonc_to_json_iter;
+    /// let jsonc = "{\"a\": [1, 2,]} // Comment\n{\"b\": 3}";
+    ///
+    /// let mut iter = jsonc_to_json_iter(jsonc).take_while_depth_zero();
+    /// let json = iter.by_ref().collect::<String>();
+    ///
+    /// assert_eq!(json, "{\"a\": [1, 2]}");
+    /// assert_eq!(iter.as_remaining_str(), " // Comment\n{\"b\": 3}");
+    /// ```
+    ///
+    /// [JSON]: https://www.json.org/json-en.html
+    #[inline]
+    pub fn take_while_depth_zero(self) -> TakeWhileDepthZero<'jsonc> {
+        TakeWhileDepthZero {
+            iter: self,
+            depth: 0,
+            end: None,
+        }
+    }
+}
+
+/// See [`JsonCToJsonIter::inspect_parts()`] for more information.
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct InspectJsonCIter<'jsonc, F> {
+    iter: JsonCToJsonIter<'jsonc>,
+    f: F,
+}
+
+impl<'jsonc, F> Iterator for InspectJsonCIter<'jsonc, F>
+where
+    F: FnMut(&str),
+{
+    type Item = &'jsonc str;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let part = self.iter.next()?;
+        (self.f)(part);
+        Some(part)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<F> FusedIterator for InspectJsonCIter<'_, F> where F: FnMut(&str) {}
+
+impl<F> fmt::Debug for InspectJsonCIter<'_, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InspectJsonCIter")
+            .field("iter", &self.iter)
+            .finish_non_exhaustive()
+    }
+}
+
+/// See [`JsonCToJsonIter::with_offsets()`] for more information.
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[derive(Clone, Debug)]
+pub struct OffsetJsonCIter<'jsonc> {
+    iter: JsonCToJsonIter<'jsonc>,
+    output_offset: usize,
+}
+
+impl<'jsonc> Iterator for OffsetJsonCIter<'jsonc> {
+    type Item = (usize, &'jsonc str);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let part = self.iter.next()?;
+        let offset = self.output_offset;
+        self.output_offset += part.len();
+        Some((offset, part))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl FusedIterator for OffsetJsonCIter<'_> {}
+
+/// Returns an [`Iterator`] that yields `(previous, part)` pairs of the
+/// string slices yielded by [`jsonc_to_json_iter()`], where `previous`
+/// is `None` for the first part.
+///
+/// This allows rewriting the output based on the preceding context,
+/// without collecting the whole output first.
+///
+/// # Example
+///
+/// ```rust
+/// use jsonc_to_json::jsonc_to_json_window_iter;
+///
+/// let jsonc = r#"{foo}/**/[1,2,3,,]"bar""#;
+///
+/// let pairs = jsonc_to_json_window_iter(jsonc).collect::<Vec<_>>();
+/// assert_eq!(
+///     pairs,
+///     [
+///         (None, "{foo}"),
+///         (Some("{foo}"), "[1,2,3"),
+///         (Some("[1,2,3"), "]\"bar\""),
+///     ]
+/// );
+///
+/// // Insert a separator between parts, unless they already contain one
+/// let json = jsonc_to_json_window_iter(jsonc)
+///     .map(|(prev, part)| match prev {
+///         Some(prev) if !prev.ends_with(',') && !part.starts_with([',', ']']) => format!(",{}", part),
+///         _ => part.to_owned(),
+///     })
+///     .collect::<String>();
+/// assert_eq!(json, "{foo},[1,2,3]\"bar\"");
+/// ```
+///
+/// [`jsonc_to_json_iter()`]: crate::jsonc_to_json_iter
+#[inline]
+pub fn jsonc_to_json_window_iter(jsonc: &str) -> WindowJsonCIter<'_> {
+    JsonCToJsonIter::new(jsonc).with_previous()
+}
+
+/// See [`jsonc_to_json_window_iter()`] for more information.
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[derive(Clone, Debug)]
+pub struct WindowJsonCIter<'jsonc> {
+    iter: JsonCToJsonIter<'jsonc>,
+    previous: Option<&'jsonc str>,
+}
+
+impl<'jsonc> Iterator for WindowJsonCIter<'jsonc> {
+    type Item = (Option<&'jsonc str>, &'jsonc str);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let part = self.iter.next()?;
+        Some((self.previous.replace(part), part))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl FusedIterator for WindowJsonCIter<'_> {}
+
+/// See [`JsonCToJsonIter::take_while_depth_zero()`] for more information.
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[derive(Clone, Debug)]
+pub struct TakeWhileDepthZero<'jsonc> {
+    iter: JsonCToJsonIter<'jsonc>,
+    /// Current bracket depth.
+    depth: usize,
+    /// Byte offset of the end of the top-level value in the
+    /// input, when it is complete.
+    end: Option<usize>,
+}
+
+impl<'jsonc> TakeWhileDepthZero<'jsonc> {
+    /// Returns the part of the input after the top-level value, if the
+    /// value is complete. Otherwise, returns the part of the input which
+    /// has not been yielded yet, see [`JsonCToJsonIter::as_remaining_str()`].
+    ///
+    /// Unlike [`JsonCToJsonIter::as_remaining_str()`], comments and trailing
+    /// commas after the top-level value are included.
+    #[must_use]
+    pub fn as_remaining_str(&self) -> &'jsonc str {
+        match self.end {
+            Some(end) => &self.iter.tokens.text()[end..],
+            None => self.iter.as_remaining_str(),
+        }
+    }
+
+    /// Returns the length of `part` up until the end of the top-level
+    /// value, or `None` if the top-level value continues after `part`.
+    fn value_end(&mut self, part: &str) -> Option<usize> {
+        for (tok, span) in JsonCTokens::new(part) {
+            match tok {
+                JsonCToken::Delim => match span.as_str() {
+                    "{" | "[" => self.depth += 1,
+                    _ => {
+                        self.depth = self.depth.saturating_sub(1);
+                        if self.depth == 0 {
+                            return Some(span.end());
+                        }
+                    }
+                },
+                JsonCToken::String
+                | JsonCToken::Number
+                | JsonCToken::Null
+                | JsonCToken::True
+                | JsonCToken::False
+                | JsonCToken::Unknown
+                    if self.depth == 0 =>
+                {
+                    return Some(span.end());
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+}
+
+impl<'jsonc> Iterator for TakeWhileDepthZero<'jsonc> {
+    type Item = &'jsonc str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.end.is_some() {
+            return None;
+        }
+
+        let part = self.iter.next()?;
+        match self.value_end(part) {
+            Some(len) => {
+                let start = (part.as_ptr() as usize) - (self.iter.tokens.text().as_ptr() as usize);
+                self.end = Some(start + len);
+                Some(&part[..len])
+            }
+            None => Some(part),
+        }
+    }
+}
+
+impl FusedIterator for TakeWhileDepthZero<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::jsonc_to_json_iter;
+
+    #[test]
+    fn test_inspect_parts() {
+        let jsonc = "[1, /* a */ 2, // b\n 3,]";
+
+        let mut inspected = Vec::new();
+        let mut iter = jsonc_to_json_iter(jsonc).inspect_parts(|part| inspected.push(part.len()));
+        assert_eq!(iter.size_hint(), jsonc_to_json_iter(jsonc).size_hint());
+
+        let parts = iter.by_ref().collect::<Vec<_>>();
+        drop(iter);
+
+        assert_eq!(parts, jsonc_to_json_iter(jsonc).collect::<Vec<_>>());
+        assert_eq!(
+            inspected,
+            parts.iter().map(|part| part.len()).collect::<Vec<_>>()
+        );
+
+        // `Iterator::inspect()` is not shadowed
+        let count = jsonc_to_json_iter(jsonc).inspect(|_: &&str| {}).count();
+        assert_eq!(count, parts.len());
+    }
+
+    #[test]
+    fn test_window_iter() {
+        assert_eq!(jsonc_to_json_window_iter("").next(), None);
+        assert_eq!(
+            jsonc_to_json_window_iter("[1]").collect::<Vec<_>>(),
+            [(None, "[1]")]
+        );
+
+        let jsonc = "[1, /* a */ 2, // b\n 3,]";
+        let parts = jsonc_to_json_iter(jsonc).collect::<Vec<_>>();
+        let pairs = jsonc_to_json_window_iter(jsonc).collect::<Vec<_>>();
+        assert_eq!(pairs.len(), parts.len());
+        for (i, &(prev, part)) in pairs.iter().enumerate() {
+            assert_eq!(prev, i.checked_sub(1).map(|i| parts[i]));
+            assert_eq!(part, parts[i]);
+        }
+    }
+
+    fn take_value(jsonc: &str) -> (String, &str) {
+        let mut iter = jsonc_to_json_iter(jsonc).take_while_depth_zero();
+        let json = iter.by_ref().collect::<String>();
+        (json, iter.as_remaining_str())
+    }
+
+    #[test]
+    fn test_with_offsets() {
+        let jsonc = "[1, /* a */ 2, // b\n 3,]";
+        let json = jsonc_to_json_iter(jsonc).collect::<String>();
+
+        let iter = jsonc_to_json_iter(jsonc).with_offsets();
+        assert_eq!(iter.size_hint(), jsonc_to_json_iter(jsonc).size_hint());
+
+        let mut end = 0;
+        for (offset, part) in iter {
+            assert_eq!(offset, end);
+            assert_eq!(&json[offset..(offset + part.len())], part);
+            end = offset + part.len();
+        }
+        assert_eq!(end, json.len());
+        assert_eq!(jsonc_to_json_iter("").with_offsets().next(), None);
+    }
+
+    #[test]
+    fn test_take_while_depth_zero() {
+        assert_eq!(take_value(""), (String::new(), ""));
+        assert_eq!(
+            take_value(" {\"a\": [1], \"b\": {}} [2]"),
+            (" {\"a\": [1], \"b\": {}}".to_owned(), " [2]")
+        );
+        assert_eq!(
+            take_value("/* [ */ [\"]\", /* ] */ 1,]2"),
+            (" [\"]\",  1]".to_owned(), "2")
+        );
+        assert_eq!(
+            take_value("[[1], /**/ {\"a\": [2,],},] {}"),
+            ("[[1],  {\"a\": [2]}]".to_owned(), " {}")
+        );
+        assert_eq!(take_value("123 456"), ("123".to_owned(), " 456"));
+        assert_eq!(take_value("[1, 2"), ("[1, 2".to_owned(), ""));
+    }
+}
@@ -0,0 +1,206 @@
+use std::fmt::{self, Write};
+use std::ops::Deref;
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::vars::push_escaped;
+
+/// Maximum depth of nested arrays and objects.
+const MAX_DEPTH: usize = 8;
+
+/// Maximum number of elements in each array and object.
+const MAX_LEN: usize = 4;
+
+/// Valid [JSON with Comments] generated using [`Arbitrary`], e.g. for
+/// fuzzing with [`cargo-fuzz`], producing more interesting inputs
+/// than arbitrary bytes.
+///
+/// The input consists of [JSON] values with line comments, block
+/// comments, and trailing commas inserted at random. Arrays and objects
+/// are nested at random, up to a fixed depth. The output of
+/// [`jsonc_to_json()`] is always valid [JSON], and
+/// [`jsonc_to_json_strict()`] always succeeds.
+///
+/// _Requires the `arbitrary` feature._
+///
+/// # Example
+///
+/// ```rust
+/// use arbitrary::{Arbitrary, Unstructured};
+/// use jsonc_to_json::{jsonc_to_json_strict, JsonCInput};
+///
+/// let data = b"arbitrary bytes, e.g. from a fuzzer";
+///
+/// let mut u = Unstructured::new(data);
+/// let jsonc = JsonCInput::arbitrary(&mut u)?;
+/// assert!(jsonc_to_json_strict(&jsonc).is_ok());
+/// # Ok::<(), arbitrary::Error>(())
+/// ```
+///
+/// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+/// [JSON]: https://www.json.org/json-en.html
+/// [`Arbitrary`]: https://docs.rs/arbitrary/latest/arbitrary/trait.Arbitrary.html
+/// [`cargo-fuzz`]: https://github.com/rust-fuzz/cargo-fuzz
+/// [`jsonc_to_json()`]: crate::jsonc_to_json
+/// [`jsonc_to_json_strict()`]: crate::jsonc_to_json_strict
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct JsonCInput(String);
+
+impl JsonCInput {
+    /// Returns the generated [JSON with Comments].
+    ///
+    /// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+    #[must_use]
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the generated [JSON with Comments] as a [`String`].
+    ///
+    /// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+    #[must_use]
+    #[inline]
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+impl Deref for JsonCInput {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl fmt::Display for JsonCInput {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl<'a> Arbitrary<'a> for JsonCInput {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut jsonc = String::new();
+        push_value(u, MAX_DEPTH, &mut jsonc)?;
+        push_trivia(u, &mut jsonc)?;
+        Ok(Self(jsonc))
+    }
+}
+
+fn push_value(u: &mut Unstructured<'_>, depth: usize, jsonc: &mut String) -> Result<()> {
+    push_trivia(u, jsonc)?;
+
+    let max = if depth == 0 { 4 } else { 6 };
+    match u.int_in_range(0..=max)? {
+        0 => jsonc.push_str(u.choose(&["null", "true", "false"])?),
+        1 => write!(jsonc, "{}", i64::arbitrary(u)?).unwrap(),
+        2 => {
+            let (int, frac, exp) = <(i32, u16, i8)>::arbitrary(u)?;
+            write!(jsonc, "{}.{}e{}", int, frac, exp).unwrap();
+        }
+        3 | 4 => push_string(u, jsonc)?,
+        5 => {
+            jsonc.push('[');
+            let len = u.int_in_range(0..=MAX_LEN)?;
+            for i in 0..len {
+                if i > 0 {
+                    jsonc.push(',');
+                }
+                push_value(u, depth - 1, jsonc)?;
+            }
+            push_end(u, len, jsonc)?;
+            jsonc.push(']');
+        }
+        _ => {
+            jsonc.push('{');
+            let len = u.int_in_range(0..=MAX_LEN)?;
+            for i in 0..len {
+                if i > 0 {
+                    jsonc.push(',');
+                }
+                push_trivia(u, jsonc)?;
+                push_string(u, jsonc)?;
+                push_trivia(u, jsonc)?;
+                jsonc.push(':');
+                push_value(u, depth - 1, jsonc)?;
+            }
+            push_end(u, len, jsonc)?;
+            jsonc.push('}');
+        }
+    }
+
+    Ok(())
+}
+
+/// Pushes an optional trailing comma, if the array
+/// or object is not empty, and optional trivia.
+fn push_end(u: &mut Unstructured<'_>, len: usize, jsonc: &mut String) -> Result<()> {
+    if (len > 0) && bool::arbitrary(u)? {
+        push_trivia(u, jsonc)?;
+        jsonc.push(',');
+    }
+    push_trivia(u, jsonc)
+}
+
+fn push_string(u: &mut Unstructured<'_>, jsonc: &mut String) -> Result<()> {
+    jsonc.push('"');
+    push_escaped(jsonc, <&str>::arbitrary(u)?);
+    jsonc.push('"');
+    Ok(())
+}
+
+/// Pushes nothing, whitespace, or a comment.
+fn push_trivia(u: &mut Unstructured<'_>, jsonc: &mut String) -> Result<()> {
+    match u.int_in_range(0..=4)? {
+        0 => {}
+        1 => jsonc.push_str(u.choose(&[" ", "\n", "\r\n", "\t"])?),
+        2 => {
+            let text = <&str>::arbitrary(u)?;
+            jsonc.push_str("//");
+            jsonc.extend(text.chars().filter(|&c| !matches!(c, '\n' | '\r')));
+            jsonc.push('\n');
+        }
+        _ => {
+            let text = <&str>::arbitrary(u)?;
+            let text = text.replace("*/", "* /");
+            jsonc.push_str("/*");
+            jsonc.push_str(&text);
+            jsonc.push_str("*/");
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{jsonc_to_json, jsonc_to_json_strict};
+
+    #[test]
+    fn test_arbitrary_is_valid() {
+        // Deterministic pseudo-random bytes
+        let mut state = 0x2545_f491_u32;
+        let data = (0..1 << 16)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                state as u8
+            })
+            .collect::<Vec<_>>();
+
+        for start in (0..data.len()).step_by(256) {
+            let mut u = Unstructured::new(&data[start..]);
+            let jsonc = JsonCInput::arbitrary(&mut u).unwrap();
+
+            let json = jsonc_to_json_strict(&jsonc);
+            assert!(json.is_ok(), "{:?}: {:?}", jsonc, json);
+            assert_eq!(json.unwrap(), jsonc_to_json(&jsonc));
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use std::io;
+
+use async_std::io::{Read, ReadExt};
+
+use crate::stream::StreamStripper;
+
+/// Reads [JSON with Comments] from an [`async_std`] `reader` until EOF,
+/// and converts it into [JSON].
+///
+/// The input is read and converted in chunks, i.e. the input is not
+/// buffered as a whole before converting. Comments, strings, and
+/// trailing commas are allowed to span across chunks.
+///
+/// The output is the same as [`jsonc_to_json()`], given the same input.
+///
+/// Returns an [`io::Error`] if reading fails, or if the
+/// output is not valid UTF-8.
+///
+/// _Requires the `async-std` feature._
+///
+/// # Example
+///
+/// ```rust
+/// use jsonc_to_json::jsonc_to_json_from_async_std_read;
+///
+/// # async fn example() -> std::io::Result<()> {
+/// let jsonc = "{\"arr\": [1, 2,/* Comment */ 3, 4,,]}// Line Comment";
+///
+/// let json = jsonc_to_json_from_async_std_read(jsonc.as_bytes()).await?;
+/// assert_eq!(json, "{\"arr\": [1, 2, 3, 4]}");
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+/// [JSON]: https://www.json.org/json-en.html
+/// [`async_std`]: https://docs.rs/async-std
+/// [`jsonc_to_json()`]: crate::jsonc_to_json
+pub async fn jsonc_to_json_from_async_std_read(
+    mut reader: impl Read + Unpin,
+) -> io::Result<String> {
+    let mut stripper = StreamStripper::new();
+    let mut json = Vec::new();
+
+    let mut chunk = [0; 8 * 1024];
+    loop {
+        let n = reader.read(&mut chunk).await?;
+        if n == 0 {
+            break;
+        }
+        stripper.feed(&chunk[..n], &mut json);
+    }
+    stripper.finish(&mut json);
+
+    String::from_utf8(json).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::future::Future;
+    use std::pin::pin;
+    use std::task::{Context, Poll, Waker};
+
+    use crate::jsonc_to_json;
+
+    /// Polls `fut` until ready, which never blocks for in-memory readers.
+    fn block_on<F: Future>(fut: F) -> F::Output {
+        let mut fut = pin!(fut);
+        let mut cx = Context::from_waker(Waker::noop());
+        loop {
+            if let Poll::Ready(output) = fut.as_mut().poll(&mut cx) {
+                return output;
+            }
+        }
+    }
+
+    #[test]
+    fn test_async_std_read() {
+        let jsonc = "{\"æøå\": [1, 2,/* Comment */ 3, 4,,]}// Line Comment";
+        let json = block_on(jsonc_to_json_from_async_std_read(jsonc.as_bytes())).unwrap();
+        assert_eq!(json, jsonc_to_json(jsonc));
+    }
+}
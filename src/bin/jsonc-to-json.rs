@@ -0,0 +1,270 @@
+//! Command line interface for converting [JSON with Comments] into [JSON].
+//!
+//! ```text
+//! cat config.jsonc | jsonc-to-json > config.json
+//! jsonc-to-json --strict --minify config.jsonc
+//! ```
+//!
+//! [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+//! [JSON]: https://www.json.org/json-en.html
+
+use std::borrow::Cow;
+use std::env;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::process::ExitCode;
+
+use jsonc_to_json::{
+    jsonc_span_iter, jsonc_to_json_selective, jsonc_to_json_strict, JsonCToken, StripFlags,
+};
+
+const USAGE: &str = "\
+Usage: jsonc-to-json [OPTIONS] [FILE]
+
+Converts JSON with Comments into JSON, by removing comments and
+trailing commas. Reads from FILE, or stdin if FILE is omitted or `-`,
+and writes the output to stdout.
+
+Options:
+      --strict                Exit with an error if the input is invalid JSONC
+      --minify                Remove all whitespace outside strings
+      --strip-only=<WHAT>     Only strip `comments` or `trailing-commas`
+      --preserve-newlines     Keep newlines of removed block comments, and
+                              keep newlines when using --minify
+  -h, --help                  Print help
+  -V, --version               Print version";
+
+#[derive(Default, Debug)]
+struct Args {
+    path: Option<String>,
+    strict: bool,
+    minify: bool,
+    flags: Option<StripFlags>,
+    preserve_newlines: bool,
+}
+
+fn parse_args(args: impl Iterator<Item = String>) -> Result<Option<Args>, String> {
+    let mut parsed = Args::default();
+
+    for arg in args {
+        match arg.as_str() {
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return Ok(None);
+            }
+            "-V" | "--version" => {
+                println!("jsonc-to-json {}", env!("CARGO_PKG_VERSION"));
+                return Ok(None);
+            }
+            "--strict" => parsed.strict = true,
+            "--minify" => parsed.minify = true,
+            "--preserve-newlines" => parsed.preserve_newlines = true,
+            "--strip-only=comments" => parsed.flags = Some(StripFlags::STRIP_COMMENTS),
+            "--strip-only=trailing-commas" => {
+                parsed.flags = Some(StripFlags::STRIP_TRAILING_COMMAS)
+            }
+            _ if arg.starts_with("--strip-only") => {
+                return Err(format!(
+                    "invalid `{}`, expected `--strip-only=comments` or `--strip-only=trailing-commas`",
+                    arg
+                ));
+            }
+            "-" => parsed.path = None,
+            _ if arg.starts_with('-') => return Err(format!("unknown option `{}`", arg)),
+            _ if parsed.path.is_some() => return Err(format!("unexpected argument `{}`", arg)),
+            _ => parsed.path = Some(arg),
+        }
+    }
+
+    Ok(Some(parsed))
+}
+
+fn read_input(path: Option<&str>) -> io::Result<String> {
+    match path {
+        Some(path) => fs::read_to_string(path),
+        None => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input)?;
+            Ok(input)
+        }
+    }
+}
+
+/// Replaces block comments containing newlines with only their newlines,
+/// such that line numbers in the output match the input.
+fn preserve_block_comment_newlines(jsonc: &str) -> Cow<'_, str> {
+    let has_multiline_comment = jsonc_span_iter(jsonc).any(|(tok, range)| {
+        (tok == JsonCToken::BlockComment) && jsonc[range].contains(['\n', '\r'])
+    });
+    if !has_multiline_comment {
+        return Cow::Borrowed(jsonc);
+    }
+
+    let mut preserved = String::with_capacity(jsonc.len());
+    for (tok, range) in jsonc_span_iter(jsonc) {
+        let s = &jsonc[range];
+        if tok == JsonCToken::BlockComment {
+            preserved.extend(s.chars().filter(|&c| (c == '\n') || (c == '\r')));
+        } else {
+            preserved.push_str(s);
+        }
+    }
+    Cow::Owned(preserved)
+}
+
+/// Removes all whitespace outside strings. Newlines are kept if
+/// `preserve_newlines` is `true`, or if they terminate a line comment.
+fn minify(json: &str, preserve_newlines: bool) -> String {
+    let mut minified = String::with_capacity(json.len());
+
+    let mut prev = None;
+    for (tok, range) in jsonc_span_iter(json) {
+        let s = &json[range];
+        if tok == JsonCToken::Space {
+            if preserve_newlines {
+                minified.extend(s.chars().filter(|&c| (c == '\n') || (c == '\r')));
+            } else if prev == Some(JsonCToken::LineComment) {
+                minified.push('\n');
+            }
+        } else {
+            minified.push_str(s);
+        }
+        prev = Some(tok);
+    }
+
+    minified
+}
+
+/// Converts `input` according to `args`, i.e. without validating it.
+fn convert<'a>(input: &'a str, args: &Args) -> Cow<'a, str> {
+    let flags = args.flags.unwrap_or(StripFlags::ALL);
+
+    let input = if args.preserve_newlines && flags.contains(StripFlags::STRIP_BLOCK_COMMENTS) {
+        preserve_block_comment_newlines(input)
+    } else {
+        Cow::Borrowed(input)
+    };
+
+    let json = match input {
+        Cow::Borrowed(input) => jsonc_to_json_selective(input, flags),
+        Cow::Owned(input) => Cow::Owned(jsonc_to_json_selective(&input, flags).into_owned()),
+    };
+    if args.minify {
+        Cow::Owned(minify(&json, args.preserve_newlines))
+    } else {
+        json
+    }
+}
+
+fn run(args: Args) -> Result<(), String> {
+    let path = args.path.as_deref();
+    let input = read_input(path).map_err(|err| match path {
+        Some(path) => format!("failed to read `{}`: {}", path, err),
+        None => format!("failed to read stdin: {}", err),
+    })?;
+
+    if args.strict {
+        jsonc_to_json_strict(&input).map_err(|err| err.to_string())?;
+    }
+
+    let json = convert(&input, &args);
+
+    let mut stdout = io::stdout().lock();
+    stdout
+        .write_all(json.as_bytes())
+        .and_then(|()| stdout.flush())
+        .map_err(|err| format!("failed to write stdout: {}", err))
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(env::args().skip(1)) {
+        Ok(Some(args)) => args,
+        Ok(None) => return ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}\n\n{}", err, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    match run(args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Option<Args>, String> {
+        parse_args(args.iter().map(|&arg| arg.to_owned()))
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let args = parse(&["--strict", "--strip-only=comments", "config.jsonc"])
+            .unwrap()
+            .unwrap();
+        assert!(args.strict);
+        assert_eq!(args.flags, Some(StripFlags::STRIP_COMMENTS));
+        assert_eq!(args.path.as_deref(), Some("config.jsonc"));
+
+        assert!(parse(&["--help"]).unwrap().is_none());
+
+        let err = parse(&["--unknown"]).unwrap_err();
+        assert_eq!(err, "unknown option `--unknown`");
+
+        let err = parse(&["--strip-only=whitespace"]).unwrap_err();
+        assert!(
+            err.starts_with("invalid `--strip-only=whitespace`"),
+            "{}",
+            err
+        );
+
+        let err = parse(&["a.jsonc", "b.jsonc"]).unwrap_err();
+        assert_eq!(err, "unexpected argument `b.jsonc`");
+    }
+
+    #[test]
+    fn test_minify_line_comment() {
+        let json = minify("[1, // Comment\n  2,\n  3]", false);
+        assert_eq!(json, "[1,// Comment\n2,3]");
+
+        let json = minify("[1,\n  2]", true);
+        assert_eq!(json, "[1,\n2]");
+    }
+
+    #[test]
+    fn test_preserve_newlines_strip_only() {
+        let jsonc = "[1, /* a\n b */ 2,] // c";
+
+        let args = Args {
+            preserve_newlines: true,
+            ..Args::default()
+        };
+        assert_eq!(convert(jsonc, &args), "[1, \n 2] ");
+
+        let args = Args {
+            preserve_newlines: true,
+            flags: Some(StripFlags::STRIP_TRAILING_COMMAS),
+            ..Args::default()
+        };
+        assert_eq!(convert(jsonc, &args), "[1, /* a\n b */ 2] // c");
+
+        let args = Args {
+            preserve_newlines: true,
+            flags: Some(StripFlags::STRIP_COMMENTS),
+            ..Args::default()
+        };
+        assert_eq!(convert(jsonc, &args), "[1, \n 2,] ");
+
+        let args = Args {
+            preserve_newlines: true,
+            ..Args::default()
+        };
+        assert_eq!(convert("[1, /** a\n b **/ 2]", &args), "[1, \n 2]");
+    }
+}
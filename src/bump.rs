@@ -0,0 +1,67 @@
+use bumpalo::Bump;
+
+use crate::JsonCToJsonIter;
+
+/// Same as [`jsonc_to_json()`], but allocates the output [JSON] in
+/// the `bump` arena, instead of allocating a [`String`].
+///
+/// If `jsonc` is already valid [JSON], then `jsonc` is returned,
+/// i.e. without allocating in `bump`.
+///
+/// _Requires the `bumpalo` feature._
+///
+/// # Example
+///
+/// ```rust
+/// use bumpalo::Bump;
+/// use jsonc_to_json::jsonc_to_json_in;
+///
+/// let bump = Bump::new();
+///
+/// let jsonc = "{\"arr\": [1, 2,/* Comment */ 3, 4,,]}// Line Comment";
+/// let json = jsonc_to_json_in(jsonc, &bump);
+/// assert_eq!(json, "{\"arr\": [1, 2, 3, 4]}");
+/// ```
+///
+/// [JSON]: https://www.json.org/json-en.html
+/// [`jsonc_to_json()`]: crate::jsonc_to_json
+#[must_use = "this returns the converted JSON; dropping it does nothing"]
+pub fn jsonc_to_json_in<'bump>(jsonc: &'bump str, bump: &'bump Bump) -> &'bump str {
+    let mut iter = JsonCToJsonIter::new(jsonc);
+
+    let first = match iter.next() {
+        Some(first) => first,
+        None => return "",
+    };
+
+    let second = match iter.next() {
+        Some(second) => second,
+        None => return first,
+    };
+
+    let mut json = bumpalo::collections::String::with_capacity_in(jsonc.len(), bump);
+    json.push_str(first);
+    json.push_str(second);
+
+    for part in iter {
+        json.push_str(part);
+    }
+
+    json.into_bump_str()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bump_borrowed() {
+        let bump = Bump::new();
+
+        let jsonc = r#"{"arr": [1, 2, 3, 4]} // Line Comment"#;
+        let json = jsonc_to_json_in(jsonc, &bump);
+        assert_eq!(json, r#"{"arr": [1, 2, 3, 4]} "#);
+        assert_eq!(json.as_ptr(), jsonc.as_ptr());
+        assert_eq!(bump.allocated_bytes_including_metadata(), 0);
+    }
+}
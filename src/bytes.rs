@@ -0,0 +1,249 @@
+use std::borrow::Cow;
+use std::iter::{self, FusedIterator};
+use std::str::{self, Utf8Error};
+
+use any_lexer::JsonCToken;
+
+use crate::stream::StreamStripper;
+use crate::tokens::JsonCTokens;
+use crate::{jsonc_to_json, JsonCError};
+
+/// Same as [`jsonc_to_json()`], but converts bytes, e.g. an HTTP body,
+/// without first validating that all of `input` is UTF-8.
+///
+/// Only the bytes in the output are validated, i.e. invalid UTF-8
+/// inside removed comments is ignored. This avoids a separate pass
+/// over `input`, e.g. using [`str::from_utf8()`], beforehand.
+///
+/// If nothing is removed, then `input` is returned as [`Cow::Borrowed`].
+///
+/// Returns [`Utf8Error`] if the output is not valid UTF-8. The offsets
+/// of the error, e.g. [`Utf8Error::valid_up_to()`], refer to the output
+/// and not `input`.
+///
+/// # Example
+///
+/// ```rust
+/// use jsonc_to_json::jsonc_to_json_from_bytes;
+///
+/// let jsonc = b"[1, /* \xFF */ 2,]";
+/// let json = jsonc_to_json_from_bytes(jsonc)?;
+/// assert_eq!(&*json, b"[1,  2]");
+///
+/// assert!(jsonc_to_json_from_bytes(b"[\"\xFF\"]").is_err());
+/// # Ok::<(), std::str::Utf8Error>(())
+/// ```
+///
+/// [`jsonc_to_json()`]: crate::jsonc_to_json
+pub fn jsonc_to_json_from_bytes(input: &[u8]) -> Result<Cow<'_, [u8]>, Utf8Error> {
+    let mut json = Vec::with_capacity(input.len());
+
+    let mut stripper = StreamStripper::new();
+    stripper.feed(input, &mut json);
+    stripper.finish(&mut json);
+
+    str::from_utf8(&json)?;
+
+    // The output is a subsequence of the input,
+    // so it is only the same length if nothing was removed
+    if json.len() == input.len() {
+        Ok(Cow::Borrowed(input))
+    } else {
+        Ok(Cow::Owned(json))
+    }
+}
+
+/// Same as [`jsonc_to_json()`], but converts bytes, replacing any invalid
+/// UTF-8 sequences with [`U+FFFD REPLACEMENT CHARACTER`], i.e. using the
+/// same semantics as [`String::from_utf8_lossy()`].
+///
+/// If `input` is valid UTF-8, then this is the same as [`jsonc_to_json()`],
+/// including returning [`Cow::Borrowed`] when possible.
+///
+/// # Example
+///
+/// ```rust
+/// use jsonc_to_json::jsonc_to_json_from_bytes_lossy;
+///
+/// let jsonc = b"[\"\xFF\", /* Comment */ 2,]";
+/// let json = jsonc_to_json_from_bytes_lossy(jsonc);
+/// assert_eq!(json, "[\"\u{FFFD}\",  2]");
+/// ```
+///
+/// [`U+FFFD REPLACEMENT CHARACTER`]: char::REPLACEMENT_CHARACTER
+/// [`jsonc_to_json()`]: crate::jsonc_to_json
+#[must_use = "this returns the converted JSON; dropping it does nothing"]
+pub fn jsonc_to_json_from_bytes_lossy(input: &[u8]) -> Cow<'_, str> {
+    match String::from_utf8_lossy(input) {
+        Cow::Borrowed(jsonc) => jsonc_to_json(jsonc),
+        Cow::Owned(jsonc) => Cow::Owned(jsonc_to_json(&jsonc).into_owned()),
+    }
+}
+
+/// Splits `stream` into records separated by null bytes, i.e. `\0`,
+/// and converts each record using [`jsonc_to_json()`].
+///
+/// Each record is validated as UTF-8 independently, i.e. invalid UTF-8
+/// in one record results in [`JsonCError::InvalidUtf8`] for that record.
+/// A null byte terminating the last record does not result in an
+/// additional empty record.
+///
+/// A null byte inside a string is not allowed, as [JSON] strings cannot
+/// contain unescaped control characters, and results in
+/// [`JsonCError::UnterminatedString`]. The boundaries of the following
+/// records are then unknown, so the iterator ends after the error.
+///
+/// All byte offsets of errors refer to `stream`.
+///
+/// # Example
+///
+/// ```rust
+/// use jsonc_to_json::{jsonc_to_json_null_delimited, JsonCError};
+///
+/// let stream = b"[1, 2,]\0{\"a\": 3} // Comment\0";
+///
+/// let records = jsonc_to_json_null_delimited(stream).collect::<Result<Vec<_>, _>>()?;
+/// assert_eq!(records, ["[1, 2]", "{\"a\": 3} "]);
+///
+/// let stream = b"[1]\0[\"a\0b\"]\0[2]";
+///
+/// let mut records = jsonc_to_json_null_delimited(stream);
+/// assert_eq!(records.next(), Some(Ok("[1]".into())));
+/// assert_eq!(
+///     records.next(),
+///     Some(Err(JsonCError::UnterminatedString { byte_offset: 5 }))
+/// );
+/// assert_eq!(records.next(), None);
+/// # Ok::<(), JsonCError>(())
+/// ```
+///
+/// [JSON]: https://www.json.org/json-en.html
+/// [`jsonc_to_json()`]: crate::jsonc_to_json
+pub fn jsonc_to_json_null_delimited(
+    stream: &[u8],
+) -> impl FusedIterator<Item = Result<Cow<'_, str>, JsonCError>> + '_ {
+    let mut start = 0;
+    let mut done = false;
+
+    iter::from_fn(move || {
+        if done || (start == stream.len()) {
+            return None;
+        }
+
+        let rest = &stream[start..];
+        let (record, terminated) = match rest.iter().position(|&b| b == b'\0') {
+            Some(end) => (&rest[..end], true),
+            None => (rest, false),
+        };
+        let record_start = start;
+        start += record.len() + usize::from(terminated);
+
+        let res = match str::from_utf8(record) {
+            Ok(jsonc) => match terminated.then(|| unterminated_string(jsonc)).flatten() {
+                Some(offset) => Err(JsonCError::UnterminatedString {
+                    byte_offset: record_start + offset,
+                }),
+                None => Ok(jsonc_to_json(jsonc)),
+            },
+            Err(err) => Err(JsonCError::InvalidUtf8 {
+                byte_offset: record_start + err.valid_up_to(),
+            }),
+        };
+        done = matches!(res, Err(JsonCError::UnterminatedString { .. }));
+
+        Some(res)
+    })
+    .fuse()
+}
+
+/// Returns the byte offset of the start of the last string in `jsonc`,
+/// if it is unterminated, i.e. if `jsonc` ends inside a string.
+fn unterminated_string(jsonc: &str) -> Option<usize> {
+    let (tok, span) = JsonCTokens::new(jsonc).last()?;
+    if tok != JsonCToken::String {
+        return None;
+    }
+
+    // The closing `"` must not be escaped by an odd number of `\`
+    let s = span.as_str();
+    let terminated = (s.len() >= 2)
+        && s.ends_with('"')
+        && (s[1..(s.len() - 1)]
+            .bytes()
+            .rev()
+            .take_while(|&b| b == b'\\')
+            .count()
+            % 2
+            == 0);
+
+    (!terminated).then(|| span.start())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_bytes_matches_jsonc_to_json() {
+        let inputs = [
+            "",
+            "[1, 2]",
+            "{\"æ\": [1, /* ø */ 2,,], // 日本語\n}",
+            "{foo}/**/[1,2,3,,]\"bar\"",
+            "[\"/* not a comment */\", 1,]",
+            "[1,\u{a0}]",
+            "{\"a\": 1,\u{2028}}",
+        ];
+        for jsonc in inputs {
+            let expected = jsonc_to_json(jsonc);
+            let json = jsonc_to_json_from_bytes(jsonc.as_bytes()).unwrap();
+            assert_eq!(&*json, expected.as_bytes(), "{:?}", jsonc);
+            assert_eq!(
+                matches!(json, Cow::Borrowed(_)),
+                expected.len() == jsonc.len(),
+                "{:?}",
+                jsonc
+            );
+            assert_eq!(jsonc_to_json_from_bytes_lossy(jsonc.as_bytes()), expected);
+        }
+    }
+
+    #[test]
+    fn test_from_bytes_invalid_utf8() {
+        let json = jsonc_to_json_from_bytes(b"[1, // \xC3\n2]").unwrap();
+        assert_eq!(&*json, b"[1, \n2]");
+
+        let err = jsonc_to_json_from_bytes(b"/* \xFF */ [\"a\xFF\"]").unwrap_err();
+        assert_eq!(err.valid_up_to(), 4);
+    }
+
+    #[test]
+    fn test_null_delimited() {
+        let records = |stream| jsonc_to_json_null_delimited(stream).collect::<Vec<_>>();
+
+        assert_eq!(records(b""), []);
+        assert_eq!(records(b"\0"), [Ok("".into())]);
+        assert_eq!(
+            records(b"[1]\0\0[2,]"),
+            [Ok("[1]".into()), Ok("".into()), Ok("[2]".into())]
+        );
+        assert_eq!(
+            records(b"[\"\\\\\"]\0[\"/*\\\"\"] // \"\0"),
+            [Ok("[\"\\\\\"]".into()), Ok("[\"/*\\\"\"] ".into())]
+        );
+        assert_eq!(
+            records(b"[1]\0[\"\xFF\"]\0[2]"),
+            [
+                Ok("[1]".into()),
+                Err(JsonCError::InvalidUtf8 { byte_offset: 6 }),
+                Ok("[2]".into())
+            ]
+        );
+        assert_eq!(
+            records(b"[\"a\\\"\0\"]\0[2]"),
+            [Err(JsonCError::UnterminatedString { byte_offset: 1 })]
+        );
+        // Like jsonc_to_json(), an unterminated string in the last record is not an error
+        assert_eq!(records(b"[\"a"), [Ok("[\"a".into())]);
+    }
+}
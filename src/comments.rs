@@ -0,0 +1,430 @@
+use std::ops::Range;
+
+use any_lexer::JsonCToken;
+
+use crate::tokens::JsonCTokens;
+use crate::{JsonCToJsonIter, Removed};
+
+/// Comment extracted from [JSON with Comments],
+/// see [`jsonc_extract_comments()`].
+///
+/// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct ExtractedComment<'jsonc> {
+    /// Whether the comment is a line comment or a block comment.
+    pub kind: CommentKind,
+    /// The text of the comment, excluding `//`, `/*`, and `*/`.
+    pub text: &'jsonc str,
+    /// Byte range of the whole comment, including delimiters.
+    pub range: Range<usize>,
+    /// Byte range of the first token after the comment, which is
+    /// not whitespace or another comment. `None` if the comment is
+    /// not followed by any such token.
+    pub associated_token: Option<Range<usize>>,
+}
+
+/// Kind of comment, see [`ExtractedComment`].
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum CommentKind {
+    /// Line comment, e.g. `// Line Comment`.
+    Line,
+    /// Block comment, e.g. `/* Block Comment */`.
+    Block,
+}
+
+/// Returns all comments in `jsonc`, ordered by their position in the input.
+///
+/// Each comment is associated with the first token following it, which
+/// is not whitespace or another comment. Consecutive comments are thereby
+/// associated with the same token. This is the same heuristic language
+/// servers use, to treat comments as documentation for the field after them.
+///
+/// This is a separate pass over `jsonc`, and is independent of
+/// the conversion performed by [`jsonc_to_json()`].
+///
+/// # Example
+///
+/// ```rust
+/// use jsonc_to_json::{jsonc_extract_comments, CommentKind};
+///
+/// let jsonc = r#"{
+///     // Size of a tab in spaces
+///     "tabSize": 4,
+///     /* Theme */ "theme": "dark"
+/// }"#;
+///
+/// let comments = jsonc_extract_comments(jsonc);
+/// assert_eq!(comments.len(), 2);
+///
+/// assert_eq!(comments[0].kind, CommentKind::Line);
+/// assert_eq!(comments[0].text, " Size of a tab in spaces");
+/// let token = comments[0].associated_token.clone().unwrap();
+/// assert_eq!(&jsonc[token], "\"tabSize\"");
+///
+/// assert_eq!(comments[1].kind, CommentKind::Block);
+/// assert_eq!(comments[1].text, " Theme ");
+/// let token = comments[1].associated_token.clone().unwrap();
+/// assert_eq!(&jsonc[token], "\"theme\"");
+/// ```
+///
+/// [`jsonc_to_json()`]: crate::jsonc_to_json
+#[must_use]
+pub fn jsonc_extract_comments(jsonc: &str) -> Vec<ExtractedComment<'_>> {
+    let mut comments: Vec<ExtractedComment<'_>> = Vec::new();
+
+    // Index of the first comment, which has not been associated yet
+    let mut unassociated = 0;
+
+    for (tok, span) in JsonCTokens::new(jsonc) {
+        let kind = match tok {
+            JsonCToken::Space => continue,
+            JsonCToken::LineComment => CommentKind::Line,
+            JsonCToken::BlockComment => CommentKind::Block,
+            _ => {
+                let range = span.range();
+                for comment in &mut comments[unassociated..] {
+                    comment.associated_token = Some(range.clone());
+                }
+                unassociated = comments.len();
+                continue;
+            }
+        };
+
+        let s = span.as_str();
+        let text = match kind {
+            CommentKind::Line => &s[2..],
+            // Unterminated block comments extend to the end of the input
+            CommentKind::Block => s[2..].strip_suffix("*/").unwrap_or(&s[2..]),
+        };
+
+        comments.push(ExtractedComment {
+            kind,
+            text,
+            range: span.range(),
+            associated_token: None,
+        });
+    }
+
+    comments
+}
+
+/// Comments added and removed between two versions of [JSON with Comments],
+/// see [`diff_comments()`].
+///
+/// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+#[derive(PartialEq, Eq, Clone, Default, Debug)]
+pub struct CommentDiff<'a> {
+    /// Comments in the new version, which are not in the old version,
+    /// ordered by their position in the new version.
+    pub added: Vec<ExtractedComment<'a>>,
+    /// Comments in the old version, which are not in the new version,
+    /// ordered by their position in the old version.
+    pub removed: Vec<ExtractedComment<'a>>,
+}
+
+impl CommentDiff<'_> {
+    /// Returns `true` if no comments were added or removed.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// Compares the comments in `old` and `new`, returning the comments
+/// which were added and removed.
+///
+/// The comments are extracted using [`jsonc_extract_comments()`], and
+/// compared by their text using a Myers diff, i.e. the result is the
+/// minimal number of additions and removals. Comments which are only
+/// moved relative to other comments, are reported as both removed and
+/// added. Changes to the surrounding [JSON] are ignored, i.e. changing
+/// a comment's kind or whitespace outside of it is not a change.
+///
+/// # Example
+///
+/// ```rust
+/// use jsonc_to_json::diff_comments;
+///
+/// let old = "{\n  // Size of a tab\n  \"tabSize\": 4,\n  /* Theme */ \"theme\": \"dark\"\n}";
+/// let new = "{\n  // Size of a tab\n  \"tabSize\": 2,\n  // Font size\n  \"fontSize\": 14\n}";
+///
+/// let diff = diff_comments(old, new);
+///
+/// let added = diff.added.iter().map(|c| c.text).collect::<Vec<_>>();
+/// assert_eq!(added, [" Font size"]);
+///
+/// let removed = diff.removed.iter().map(|c| c.text).collect::<Vec<_>>();
+/// assert_eq!(removed, [" Theme "]);
+/// ```
+///
+/// [JSON]: https://www.json.org/json-en.html
+#[must_use]
+pub fn diff_comments<'a>(old: &'a str, new: &'a str) -> CommentDiff<'a> {
+    let old = jsonc_extract_comments(old);
+    let new = jsonc_extract_comments(new);
+
+    let old_text = old.iter().map(|comment| comment.text).collect::<Vec<_>>();
+    let new_text = new.iter().map(|comment| comment.text).collect::<Vec<_>>();
+    let (old_kept, new_kept) = myers_diff(&old_text, &new_text);
+
+    let changed = |comments: Vec<ExtractedComment<'a>>, kept: Vec<bool>| {
+        comments
+            .into_iter()
+            .zip(kept)
+            .filter_map(|(comment, kept)| (!kept).then_some(comment))
+            .collect()
+    };
+    CommentDiff {
+        added: changed(new, new_kept),
+        removed: changed(old, old_kept),
+    }
+}
+
+/// Returns whether each element in `a` and `b` is part of the
+/// longest common subsequence of `a` and `b`, using the Myers
+/// O(ND) difference algorithm.
+fn myers_diff<T: PartialEq>(a: &[T], b: &[T]) -> (Vec<bool>, Vec<bool>) {
+    let (n, m) = (a.len() as isize, b.len() as isize);
+    let max = n + m;
+
+    let mut a_kept = vec![false; a.len()];
+    let mut b_kept = vec![false; b.len()];
+    if max == 0 {
+        return (a_kept, b_kept);
+    }
+
+    // `v[k + max]` is the furthest reaching `x` on diagonal `k`,
+    // and `trace[d]` is `v` before taking `d` edits
+    let idx = |k: isize| (k + max) as usize;
+    let mut v = vec![0isize; (2 * max + 1) as usize];
+    let mut trace = Vec::new();
+
+    'outer: for d in 0..=max {
+        trace.push(v.clone());
+        for k in (-d..=d).step_by(2) {
+            let mut x = if (k == -d) || ((k != d) && (v[idx(k - 1)] < v[idx(k + 1)])) {
+                v[idx(k + 1)]
+            } else {
+                v[idx(k - 1)] + 1
+            };
+            let mut y = x - k;
+            while (x < n) && (y < m) && (a[x as usize] == b[y as usize]) {
+                x += 1;
+                y += 1;
+            }
+            v[idx(k)] = x;
+            if (x >= n) && (y >= m) {
+                break 'outer;
+            }
+        }
+    }
+
+    // Backtrack through the edits, marking the diagonals as kept
+    let (mut x, mut y) = (n, m);
+    for (d, v) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        let k = x - y;
+        let prev_k = if (k == -d) || ((k != d) && (v[idx(k - 1)] < v[idx(k + 1)])) {
+            k + 1
+        } else {
+            k - 1
+        };
+        let prev_x = v[idx(prev_k)];
+        let prev_y = prev_x - prev_k;
+
+        while (x > prev_x) && (y > prev_y) {
+            x -= 1;
+            y -= 1;
+            a_kept[x as usize] = true;
+            b_kept[y as usize] = true;
+        }
+
+        if d > 0 {
+            x = prev_x;
+            y = prev_y;
+        }
+    }
+
+    (a_kept, b_kept)
+}
+
+/// Same as [`jsonc_to_json()`], but instead of removing comments, each
+/// comment is replaced by the string returned by `replacer`.
+///
+/// `replacer` is called with the kind of comment and the raw comment,
+/// including `//`, `/*`, and `*/`, in the order the comments appear in
+/// `jsonc`. Trailing commas are still removed, and are not passed
+/// to `replacer`.
+///
+/// # Example
+///
+/// ```rust
+/// use jsonc_to_json::{jsonc_to_json_replace_with, CommentKind};
+///
+/// let jsonc = "[1, /* Comment */ 2,] // Line Comment";
+///
+/// // Replace comments with whitespace of the same length
+/// let json = jsonc_to_json_replace_with(jsonc, |_kind, comment| " ".repeat(comment.len()));
+/// assert_eq!(json, "[1,               2]                ");
+///
+/// let json = jsonc_to_json_replace_with(jsonc, |kind, _comment| match kind {
+///     CommentKind::Line => "",
+///     CommentKind::Block => "null,",
+/// });
+/// assert_eq!(json, "[1, null, 2] ");
+/// ```
+///
+/// [`jsonc_to_json()`]: crate::jsonc_to_json
+#[must_use = "this returns the converted JSON; dropping it does nothing"]
+pub fn jsonc_to_json_replace_with<'jsonc, F, R>(jsonc: &'jsonc str, mut replacer: F) -> String
+where
+    F: FnMut(CommentKind, &'jsonc str) -> R,
+    R: AsRef<str>,
+{
+    // Due to lookahead, comments can be removed before the string
+    // slice preceding them is yielded, so all ranges are collected
+    // first and then merged in order
+    let mut comments = Vec::new();
+    let mut on_removed = |removed, range: Range<usize>| match removed {
+        Removed::LineComment => comments.push((CommentKind::Line, range)),
+        Removed::BlockComment => comments.push((CommentKind::Block, range)),
+        Removed::TrailingComma { .. } => {}
+    };
+
+    let mut parts = Vec::new();
+    let mut iter = JsonCToJsonIter::new(jsonc);
+    while let Some(part) = iter.next_with(&mut on_removed) {
+        parts.push(part);
+    }
+
+    let mut json = String::with_capacity(jsonc.len());
+    let mut comments = comments.into_iter().peekable();
+    for part in parts {
+        let start = (part.as_ptr() as usize) - (jsonc.as_ptr() as usize);
+        while let Some((kind, range)) = comments.next_if(|(_, range)| range.start < start) {
+            json.push_str(replacer(kind, &jsonc[range]).as_ref());
+        }
+        json.push_str(part);
+    }
+    for (kind, range) in comments {
+        json.push_str(replacer(kind, &jsonc[range]).as_ref());
+    }
+
+    json
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{jsonc_to_json_diff, EditKind};
+
+    #[test]
+    fn test_replace_with() {
+        let inputs = [
+            "",
+            "// a",
+            "[1, // a\n/* b */ 2 /**/,] /* c",
+            "{\"a\": [1,/**/], /* b */}// c",
+        ];
+        for jsonc in inputs {
+            let json = jsonc_to_json_replace_with(jsonc, |_, _| "");
+            assert_eq!(json, crate::jsonc_to_json(jsonc), "{:?}", jsonc);
+
+            let mut expected = jsonc_extract_comments(jsonc).into_iter();
+            let json = jsonc_to_json_replace_with(jsonc, |kind, comment| {
+                let expected = expected.next().unwrap();
+                assert_eq!((kind, comment), (expected.kind, &jsonc[expected.range]));
+                " ".repeat(comment.len())
+            });
+            assert_eq!(expected.next(), None);
+
+            let trailing_commas = jsonc_to_json_diff(jsonc)
+                .into_iter()
+                .filter(|edit| edit.kind() == EditKind::TrailingComma)
+                .count();
+            assert_eq!(json.len(), jsonc.len() - trailing_commas, "{:?}", jsonc);
+        }
+    }
+
+    #[test]
+    fn test_extract_comments() {
+        let jsonc = "[1, // a\n/* b */ 2 /**/] /* c";
+        let comments = jsonc_extract_comments(jsonc);
+        assert_eq!(
+            comments,
+            [
+                ExtractedComment {
+                    kind: CommentKind::Line,
+                    text: " a",
+                    range: 4..8,
+                    associated_token: Some(17..18),
+                },
+                ExtractedComment {
+                    kind: CommentKind::Block,
+                    text: " b ",
+                    range: 9..16,
+                    associated_token: Some(17..18),
+                },
+                ExtractedComment {
+                    kind: CommentKind::Block,
+                    text: "",
+                    range: 19..23,
+                    associated_token: Some(23..24),
+                },
+                ExtractedComment {
+                    kind: CommentKind::Block,
+                    text: " c",
+                    range: 25..29,
+                    associated_token: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_myers_diff() {
+        let lcs_len = |a: &str, b: &str| {
+            let a = a.chars().collect::<Vec<_>>();
+            let b = b.chars().collect::<Vec<_>>();
+            let (a_kept, b_kept) = myers_diff(&a, &b);
+
+            let a_lcs = a.iter().zip(&a_kept).filter(|(_, &k)| k).map(|(c, _)| c);
+            let b_lcs = b.iter().zip(&b_kept).filter(|(_, &k)| k).map(|(c, _)| c);
+            assert!(a_lcs.clone().eq(b_lcs), "{:?} {:?}", a, b);
+            a_lcs.count()
+        };
+
+        assert_eq!(lcs_len("", ""), 0);
+        assert_eq!(lcs_len("abc", ""), 0);
+        assert_eq!(lcs_len("", "abc"), 0);
+        assert_eq!(lcs_len("abc", "abc"), 3);
+        assert_eq!(lcs_len("abc", "xyz"), 0);
+        assert_eq!(lcs_len("abcabba", "cbabac"), 4);
+        assert_eq!(lcs_len("abcdef", "abxdeyf"), 5);
+    }
+
+    #[test]
+    fn test_diff_comments() {
+        let old = "[1, /* a */ 2, /* b */ 3] // c";
+        let new = "[/* a */ 1, 2, 3, /* d */ 4] // c\n// b";
+
+        let diff = diff_comments(old, new);
+        let texts = |comments: &[ExtractedComment<'static>]| {
+            comments.iter().map(|c| c.text).collect::<Vec<_>>()
+        };
+        assert_eq!(texts(&diff.added), [" d ", " b"]);
+        assert_eq!(texts(&diff.removed), [" b "]);
+        assert_eq!(diff.added[0].range, 18..25);
+
+        assert!(diff_comments(old, old).is_empty());
+        assert!(diff_comments("// a\n[1]", "[2] /* a*/").is_empty());
+        assert!(diff_comments("[1]", "[2] /**/").removed.is_empty());
+    }
+
+    #[test]
+    fn test_extract_no_comments() {
+        assert_eq!(jsonc_extract_comments(""), []);
+        assert_eq!(jsonc_extract_comments("{\"a\": \"// b /* c */\"}"), []);
+    }
+}
@@ -0,0 +1,65 @@
+use compact_str::CompactString;
+
+use crate::JsonCToJsonIter;
+
+const MAX_INLINE_LEN: usize = std::mem::size_of::<CompactString>();
+
+/// Same as [`jsonc_to_json()`], but returns a [`CompactString`].
+///
+/// If the output [JSON] is at most 24 bytes, then it is stored
+/// inline, i.e. without allocating.
+///
+/// _Requires the `compact_str` feature._
+///
+/// # Example
+///
+/// ```rust
+/// use jsonc_to_json::jsonc_to_json_compact;
+///
+/// let jsonc = "[1, 2, /* Comment */ 3,]";
+/// let json = jsonc_to_json_compact(jsonc);
+/// assert_eq!(json, "[1, 2,  3]");
+/// assert!(!json.is_heap_allocated());
+/// ```
+///
+/// [JSON]: https://www.json.org/json-en.html
+/// [`jsonc_to_json()`]: crate::jsonc_to_json
+/// [`CompactString`]: https://docs.rs/compact_str/latest/compact_str/struct.CompactString.html
+#[must_use = "this returns the converted JSON; dropping it does nothing"]
+pub fn jsonc_to_json_compact(jsonc: &str) -> CompactString {
+    let iter = JsonCToJsonIter::new(jsonc);
+
+    let mut json = CompactString::with_capacity(iter.remaining_len());
+    json.extend(iter);
+
+    // Moves the output inline, if the input was too long to be
+    // inline, but the output is short enough
+    if json.len() <= MAX_INLINE_LEN {
+        json.shrink_to_fit();
+    }
+
+    json
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::jsonc_to_json;
+
+    #[test]
+    fn test_compact_heap() {
+        let jsonc = "{\"arr\": [1, 2,/* Comment */ 3, 4,,], \"foo\": \"bar\"}// Line Comment";
+        let json = jsonc_to_json_compact(jsonc);
+        assert_eq!(json, jsonc_to_json(jsonc));
+        assert!(json.is_heap_allocated());
+    }
+
+    #[test]
+    fn test_compact_inline() {
+        let jsonc = "[1, 2, 3] // Line Comment that is longer than 24 bytes";
+        let json = jsonc_to_json_compact(jsonc);
+        assert_eq!(json, jsonc_to_json(jsonc));
+        assert!(!json.is_heap_allocated());
+    }
+}
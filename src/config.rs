@@ -0,0 +1,610 @@
+use std::borrow::Cow;
+
+use any_lexer::JsonCToken;
+
+use crate::duplicate::duplicate_key_removals;
+use crate::instrument::instrument;
+#[cfg(feature = "from_json5")]
+use crate::json5::Json5;
+use crate::tokens::JsonCTokens;
+use crate::{
+    collect_cow, CommentKind, DuplicateKeyPolicy, JsonCError, JsonCToJsonIter, StripFlags,
+};
+
+/// Configuration for converting [JSON with Comments] into [JSON],
+/// see [`jsonc_to_json_with_config()`].
+///
+/// The default configuration produces the same output
+/// as [`jsonc_to_json()`].
+///
+/// # Example
+///
+/// ```rust
+/// use jsonc_to_json::{jsonc_to_json_with_config, JsonCConfig};
+///
+/// let jsonc = "[1, /* outer /* inner */ still-outer */ 2]";
+///
+/// let config = JsonCConfig::new().nested_block_comments(true);
+///
+/// let json = jsonc_to_json_with_config(jsonc, &config)?;
+/// assert_eq!(json, "[1,  2]");
+/// # Ok::<(), jsonc_to_json::JsonCError>(())
+/// ```
+///
+/// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+/// [JSON]: https://www.json.org/json-en.html
+/// [`jsonc_to_json()`]: crate::jsonc_to_json
+#[derive(Clone, Debug)]
+pub struct JsonCConfig {
+    pub(crate) flags: StripFlags,
+    pub(crate) nested_block_comments: bool,
+    pub(crate) line_ending: LineEnding,
+    pub(crate) max_input_bytes: usize,
+    pub(crate) header_comment: Option<String>,
+    pub(crate) header_comment_kind: CommentKind,
+    pub(crate) duplicate_key_policy: DuplicateKeyPolicy,
+    #[cfg(feature = "from_json5")]
+    pub(crate) json5: Json5,
+}
+
+impl Default for JsonCConfig {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl JsonCConfig {
+    /// Creates a new default `JsonCConfig`.
+    #[must_use]
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            flags: StripFlags::default(),
+            nested_block_comments: false,
+            line_ending: LineEnding::default(),
+            max_input_bytes: usize::MAX,
+            header_comment: None,
+            header_comment_kind: CommentKind::Block,
+            duplicate_key_policy: DuplicateKeyPolicy::Allow,
+            #[cfg(feature = "from_json5")]
+            json5: Json5::default(),
+        }
+    }
+
+    /// Specifies which [JSON with Comments] features to strip.
+    ///
+    /// Defaults to [`StripFlags::ALL`].
+    ///
+    /// See [`jsonc_to_json_selective()`] for more information.
+    ///
+    /// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+    /// [`jsonc_to_json_selective()`]: crate::jsonc_to_json_selective
+    #[must_use]
+    #[inline]
+    pub fn strip(mut self, flags: StripFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Specifies whether block comments can be nested, i.e. whether
+    /// `/* outer /* inner */ still-outer */` is a single block comment.
+    ///
+    /// Standard [JSON with Comments] does not support nested block comments,
+    /// in which case the block comment ends at the first `*/`.
+    ///
+    /// Defaults to `false`.
+    ///
+    /// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+    #[must_use]
+    #[inline]
+    pub fn nested_block_comments(mut self, nested: bool) -> Self {
+        self.nested_block_comments = nested;
+        self
+    }
+
+    /// Specifies how line endings are normalized in the output.
+    ///
+    /// Line endings inside strings are never altered.
+    ///
+    /// Defaults to [`LineEnding::Preserve`].
+    ///
+    /// **Note:** Normalization is applied by [`jsonc_to_json_with_config()`]
+    /// as a separate pass over the output, and is not applied by [`iter()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use jsonc_to_json::{jsonc_to_json_with_config, JsonCConfig, LineEnding};
+    ///
+    /// let jsonc = "[\r\n  1, // Comment\r\n  2,\n]";
+    ///
+    /// let config = JsonCConfig::new().normalize_line_endings(LineEnding::Lf);
+    /// let json = jsonc_to_json_with_config(jsonc, &config)?;
+    /// assert_eq!(json, "[\n  1, \n  2\n]");
+    ///
+    /// let config = JsonCConfig::new().normalize_line_endings(LineEnding::CrLf);
+    /// let json = jsonc_to_json_with_config(jsonc, &config)?;
+    /// assert_eq!(json, "[\r\n  1, \r\n  2\r\n]");
+    /// # Ok::<(), jsonc_to_json::JsonCError>(())
+    /// ```
+    ///
+    /// [`iter()`]: Self::iter
+    #[must_use]
+    #[inline]
+    pub fn normalize_line_endings(mut self, line_ending: LineEnding) -> Self {
+        self.line_ending = line_ending;
+        self
+    }
+
+    /// Specifies the maximum length of the input in bytes, for which
+    /// [`jsonc_to_json_with_config()`] returns [`JsonCError::InputTooLarge`],
+    /// before allocating anything.
+    ///
+    /// This is useful when converting untrusted input, e.g. in a server.
+    ///
+    /// Defaults to [`usize::MAX`], i.e. no limit.
+    ///
+    /// **Note:** The limit is not checked by [`iter()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use jsonc_to_json::{jsonc_to_json_with_config, JsonCConfig, JsonCError};
+    ///
+    /// let config = JsonCConfig::new().max_input_bytes(8);
+    ///
+    /// let json = jsonc_to_json_with_config("[1, 2,]", &config);
+    /// assert_eq!(json.unwrap(), "[1, 2]");
+    ///
+    /// let err = jsonc_to_json_with_config("[1, 2, 3,]", &config);
+    /// assert_eq!(err, Err(JsonCError::InputTooLarge { limit: 8, actual: 10 }));
+    /// ```
+    ///
+    /// [`iter()`]: Self::iter
+    #[must_use]
+    #[inline]
+    pub fn max_input_bytes(mut self, limit: usize) -> Self {
+        self.max_input_bytes = limit;
+        self
+    }
+
+    /// Specifies a comment, which [`json_to_jsonc_with_config()`] prepends
+    /// to the [JSON], e.g. `"Generated by <tool>"`.
+    ///
+    /// Defaults to no header comment.
+    ///
+    /// **Note:** The header comment is only used when converting [JSON]
+    /// into [JSON with Comments], and is ignored otherwise.
+    ///
+    /// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+    /// [JSON]: https://www.json.org/json-en.html
+    /// [`json_to_jsonc_with_config()`]: crate::json_to_jsonc_with_config
+    #[must_use]
+    #[inline]
+    pub fn header_comment(mut self, header: impl Into<String>) -> Self {
+        self.header_comment = Some(header.into());
+        self
+    }
+
+    /// Specifies whether the header comment is written as a block comment,
+    /// or as a line comment for each line, see [`header_comment()`].
+    ///
+    /// Defaults to [`CommentKind::Block`].
+    ///
+    /// [`header_comment()`]: Self::header_comment
+    #[must_use]
+    #[inline]
+    pub fn header_comment_kind(mut self, kind: CommentKind) -> Self {
+        self.header_comment_kind = kind;
+        self
+    }
+
+    /// Specifies how duplicate keys in objects are handled.
+    ///
+    /// Defaults to [`DuplicateKeyPolicy::Allow`], i.e. duplicate keys
+    /// are left as is, and most [JSON] parsers keep the last occurrence.
+    ///
+    /// **Note:** All other policies require a separate pass over the input,
+    /// which tracks the keys of every object, i.e. the conversion is slower.
+    /// Additionally [`DuplicateKeyPolicy::KeepFirst`] and
+    /// [`DuplicateKeyPolicy::KeepLast`] always allocate when a duplicate
+    /// is found. The policy is not applied by [`iter()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use jsonc_to_json::{jsonc_to_json_with_config, DuplicateKeyPolicy, JsonCConfig, JsonCError};
+    ///
+    /// let jsonc = "{\"a\": 1, /* Override */ \"a\": 2}";
+    ///
+    /// let config = JsonCConfig::new().on_duplicate_key(DuplicateKeyPolicy::KeepFirst);
+    /// let json = jsonc_to_json_with_config(jsonc, &config)?;
+    /// assert_eq!(json, "{\"a\": 1}");
+    ///
+    /// let config = JsonCConfig::new().on_duplicate_key(DuplicateKeyPolicy::KeepLast);
+    /// let json = jsonc_to_json_with_config(jsonc, &config)?;
+    /// assert_eq!(json, "{\"a\": 2}");
+    ///
+    /// let config = JsonCConfig::new().on_duplicate_key(DuplicateKeyPolicy::Error);
+    /// let err = jsonc_to_json_with_config(jsonc, &config);
+    /// assert_eq!(err, Err(JsonCError::DuplicateKey { byte_offset: 24 }));
+    /// # Ok::<(), jsonc_to_json::JsonCError>(())
+    /// ```
+    ///
+    /// [JSON]: https://www.json.org/json-en.html
+    /// [`iter()`]: Self::iter
+    #[must_use]
+    #[inline]
+    pub fn on_duplicate_key(mut self, policy: DuplicateKeyPolicy) -> Self {
+        self.duplicate_key_policy = policy;
+        self
+    }
+
+    /// Specifies whether [JSON5] single-quoted strings are allowed, in which
+    /// case they are turned into double-quoted strings, i.e. `'a "b" \'c\''`
+    /// is turned into `"a \"b\" 'c'"`.
+    ///
+    /// Other escape sequences are kept as is, and no other [JSON5]
+    /// features are supported.
+    ///
+    /// Defaults to `false`, i.e. single-quoted strings are left as is,
+    /// and comments inside them are removed.
+    ///
+    /// **Note:** The strings are turned into double-quoted strings
+    /// by [`jsonc_to_json_with_config()`] as a separate pass over the
+    /// input, and is not applied by [`iter()`].
+    ///
+    /// _Requires the `from_json5` feature._
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use jsonc_to_json::{jsonc_to_json_with_config, JsonCConfig};
+    ///
+    /// let jsonc = "{'url': 'https://example.com', /* Comment */ 'quote': '\"hi\"',}";
+    ///
+    /// let config = JsonCConfig::new().allow_single_quoted_strings(true);
+    /// let json = jsonc_to_json_with_config(jsonc, &config)?;
+    /// assert_eq!(json, "{\"url\": \"https://example.com\",  \"quote\": \"\\\"hi\\\"\"}");
+    /// # Ok::<(), jsonc_to_json::JsonCError>(())
+    /// ```
+    ///
+    /// [JSON5]: https://json5.org
+    /// [`iter()`]: Self::iter
+    #[cfg(feature = "from_json5")]
+    #[must_use]
+    #[inline]
+    pub fn allow_single_quoted_strings(mut self, allow: bool) -> Self {
+        self.json5.single_quoted_strings = allow;
+        self
+    }
+
+    /// Specifies whether [JSON5] unquoted object keys are allowed, in which
+    /// case they are turned into double-quoted keys, i.e. `{café: 1}` is
+    /// turned into `{"café": 1}`.
+    ///
+    /// Unquoted keys must be ECMAScript identifiers, i.e. start with a
+    /// Unicode letter (`XID_Start`), `$`, or `_`, followed by Unicode
+    /// letters, digits, and connectors (`XID_Continue`), `$`, ZWNJ, or ZWJ.
+    /// Escape sequences in identifiers are not supported. Identifiers are
+    /// only quoted when they follow `{` or `,` and are followed by `:`.
+    ///
+    /// Defaults to `false`, i.e. unquoted keys are left as is.
+    ///
+    /// **Note:** Like [`allow_single_quoted_strings()`], keys are quoted by
+    /// [`jsonc_to_json_with_config()`] as a separate pass over the
+    /// input, and is not applied by [`iter()`].
+    ///
+    /// _Requires the `from_json5` feature._
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use jsonc_to_json::{jsonc_to_json_with_config, JsonCConfig};
+    ///
+    /// let jsonc = "{café: 1, /* Comment */ 日本語: [true, null,],}";
+    ///
+    /// let config = JsonCConfig::new().allow_unquoted_unicode_keys(true);
+    /// let json = jsonc_to_json_with_config(jsonc, &config)?;
+    /// assert_eq!(json, "{\"café\": 1,  \"日本語\": [true, null]}");
+    /// # Ok::<(), jsonc_to_json::JsonCError>(())
+    /// ```
+    ///
+    /// [JSON5]: https://json5.org
+    /// [`allow_single_quoted_strings()`]: Self::allow_single_quoted_strings
+    /// [`iter()`]: Self::iter
+    #[cfg(feature = "from_json5")]
+    #[must_use]
+    #[inline]
+    pub fn allow_unquoted_unicode_keys(mut self, allow: bool) -> Self {
+        self.json5.unquoted_keys = allow;
+        self
+    }
+
+    /// Returns a [`JsonCToJsonIter`] over `jsonc` using this configuration.
+    #[inline]
+    pub fn iter<'jsonc>(&self, jsonc: &'jsonc str) -> JsonCToJsonIter<'jsonc> {
+        JsonCToJsonIter::with_config(jsonc, self)
+    }
+}
+
+/// Same as [`jsonc_to_json()`], but using the options specified by `config`.
+///
+/// Returns [`JsonCError::InputTooLarge`] if `jsonc` is longer than
+/// [`JsonCConfig::max_input_bytes()`], and [`JsonCError::DuplicateKey`]
+/// if an object contains a duplicate key when using
+/// [`DuplicateKeyPolicy::Error`].
+///
+/// See [`JsonCConfig`] for more information.
+///
+/// [`jsonc_to_json()`]: crate::jsonc_to_json
+#[inline]
+pub fn jsonc_to_json_with_config<'jsonc>(
+    jsonc: &'jsonc str,
+    config: &JsonCConfig,
+) -> Result<Cow<'jsonc, str>, JsonCError> {
+    if jsonc.len() > config.max_input_bytes {
+        return Err(JsonCError::InputTooLarge {
+            limit: config.max_input_bytes,
+            actual: jsonc.len(),
+        });
+    }
+
+    #[cfg(feature = "from_json5")]
+    if config.json5.is_enabled() {
+        let (requoted, offsets) = config.json5.to_jsonc(jsonc, config.nested_block_comments);
+        if let Cow::Owned(requoted) = requoted {
+            // Errors are relative to `requoted`, and not the input
+            let json = convert(&requoted, config)
+                .map_err(|err| err.map_byte_offset(|offset| offsets.to_input(offset)))?;
+            return Ok(Cow::Owned(json.into_owned()));
+        }
+    }
+
+    convert(jsonc, config)
+}
+
+fn convert<'jsonc>(
+    jsonc: &'jsonc str,
+    config: &JsonCConfig,
+) -> Result<Cow<'jsonc, str>, JsonCError> {
+    let tokens = JsonCTokens::new(jsonc).nested_block_comments(config.nested_block_comments);
+    let removals = duplicate_key_removals(tokens, config.duplicate_key_policy)?;
+    if !removals.is_empty() {
+        let mut deduplicated = String::with_capacity(jsonc.len());
+        let mut end = 0;
+        for range in removals {
+            deduplicated.push_str(&jsonc[end..range.start]);
+            end = range.end;
+        }
+        deduplicated.push_str(&jsonc[end..]);
+
+        let instrument = instrument!("jsonc_to_json_with_config", &deduplicated);
+        let json = collect_cow(config.iter(&deduplicated), instrument);
+        let json = normalize_line_endings(json, config.line_ending, config.nested_block_comments)
+            .into_owned();
+        return Ok(Cow::Owned(json));
+    }
+
+    let instrument = instrument!("jsonc_to_json_with_config", jsonc);
+    let json = collect_cow(config.iter(jsonc), instrument);
+    Ok(normalize_line_endings(
+        json,
+        config.line_ending,
+        config.nested_block_comments,
+    ))
+}
+
+/// Line ending used when normalizing line endings,
+/// see [`JsonCConfig::normalize_line_endings()`].
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Default, Debug)]
+pub enum LineEnding {
+    /// Line endings are left as is.
+    #[default]
+    Preserve,
+    /// All `\r\n` are turned into `\n`.
+    Lf,
+    /// All bare `\n` are turned into `\r\n`.
+    CrLf,
+}
+
+pub(crate) fn normalize_line_endings<'jsonc>(
+    json: Cow<'jsonc, str>,
+    line_ending: LineEnding,
+    nested_block_comments: bool,
+) -> Cow<'jsonc, str> {
+    let needs_normalizing = |s: &str| match line_ending {
+        LineEnding::Preserve => false,
+        LineEnding::Lf => s.contains("\r\n"),
+        LineEnding::CrLf => has_bare_lf(s),
+    };
+
+    let tokens = JsonCTokens::new(&json).nested_block_comments(nested_block_comments);
+    let needed = tokens
+        .clone()
+        .any(|(tok, span)| (tok == JsonCToken::Space) && needs_normalizing(span.as_str()));
+    if !needed {
+        return json;
+    }
+
+    let mut normalized = String::with_capacity(json.len());
+    for (tok, span) in tokens {
+        let s = span.as_str();
+        if (tok != JsonCToken::Space) || !needs_normalizing(s) {
+            normalized.push_str(s);
+            continue;
+        }
+
+        match line_ending {
+            LineEnding::Preserve => unreachable!(),
+            LineEnding::Lf => normalized.push_str(&s.replace("\r\n", "\n")),
+            LineEnding::CrLf => {
+                let mut prev = None;
+                for c in s.chars() {
+                    if (c == '\n') && (prev != Some('\r')) {
+                        normalized.push('\r');
+                    }
+                    normalized.push(c);
+                    prev = Some(c);
+                }
+            }
+        }
+    }
+
+    Cow::Owned(normalized)
+}
+
+/// Returns `true` if `s` contains a `\n` not preceded by `\r`.
+fn has_bare_lf(s: &str) -> bool {
+    let bytes = s.as_bytes();
+    bytes
+        .iter()
+        .enumerate()
+        .any(|(i, &b)| (b == b'\n') && ((i == 0) || (bytes[i - 1] != b'\r')))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::jsonc_to_json;
+
+    #[test]
+    fn test_line_endings_in_strings() {
+        let jsonc = "{\"a\r\nb\": \"c\nd\", // Comment\r\n\"e\": 1,\n}";
+
+        let config = JsonCConfig::new().normalize_line_endings(LineEnding::Lf);
+        let json = jsonc_to_json_with_config(jsonc, &config).unwrap();
+        assert_eq!(json, "{\"a\r\nb\": \"c\nd\", \n\"e\": 1\n}");
+
+        let config = JsonCConfig::new().normalize_line_endings(LineEnding::CrLf);
+        let json = jsonc_to_json_with_config(jsonc, &config).unwrap();
+        assert_eq!(json, "{\"a\r\nb\": \"c\nd\", \r\n\"e\": 1\r\n}");
+    }
+
+    #[test]
+    fn test_line_endings_borrowed() {
+        let jsonc = "[\n1,\n2\n]";
+        let config = JsonCConfig::new().normalize_line_endings(LineEnding::Lf);
+        let json = jsonc_to_json_with_config(jsonc, &config).unwrap();
+        assert!(matches!(json, Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_default_config() {
+        let jsonc = "{\"a\": [1, /* Block */ 2,,], // Line\n}";
+        let config = JsonCConfig::new();
+        assert_eq!(
+            jsonc_to_json_with_config(jsonc, &config).unwrap(),
+            jsonc_to_json(jsonc)
+        );
+    }
+
+    #[test]
+    fn test_nested_block_comments() {
+        let config = JsonCConfig::new().nested_block_comments(true);
+
+        let jsonc = "[1, /* outer /* inner */ still-outer */ 2]";
+        assert_eq!(jsonc_to_json(jsonc), "[1,  still-outer */ 2]");
+        assert_eq!(
+            jsonc_to_json_with_config(jsonc, &config).unwrap(),
+            "[1,  2]"
+        );
+
+        let jsonc = "[1, 2, /* a /* b /* c */ */ d */]";
+        assert_eq!(
+            jsonc_to_json_with_config(jsonc, &config).unwrap(),
+            "[1, 2 ]"
+        );
+
+        let jsonc = "[1, /* a /* b */ 2]";
+        assert_eq!(jsonc_to_json_with_config(jsonc, &config).unwrap(), "[1 ");
+    }
+
+    #[test]
+    fn test_max_input_bytes() {
+        let jsonc = "[1, 2,]";
+
+        let config = JsonCConfig::new().max_input_bytes(jsonc.len());
+        assert_eq!(jsonc_to_json_with_config(jsonc, &config).unwrap(), "[1, 2]");
+
+        let config = JsonCConfig::new().max_input_bytes(jsonc.len() - 1);
+        assert_eq!(
+            jsonc_to_json_with_config(jsonc, &config),
+            Err(JsonCError::InputTooLarge {
+                limit: 6,
+                actual: 7
+            })
+        );
+    }
+
+    #[cfg(feature = "from_json5")]
+    #[test]
+    fn test_single_quoted_strings() {
+        let jsonc = "{'a': 'b // c', /* 'd' */ 'a': 'e',}";
+
+        let config = JsonCConfig::new();
+        assert_eq!(
+            jsonc_to_json_with_config(jsonc, &config).unwrap(),
+            jsonc_to_json(jsonc)
+        );
+
+        let config = JsonCConfig::new().allow_single_quoted_strings(true);
+        let json = jsonc_to_json_with_config(jsonc, &config).unwrap();
+        assert_eq!(json, "{\"a\": \"b // c\",  \"a\": \"e\"}");
+
+        // Duplicate keys are detected after requoting
+        let config = config.on_duplicate_key(DuplicateKeyPolicy::KeepFirst);
+        let json = jsonc_to_json_with_config(jsonc, &config).unwrap();
+        assert_eq!(json, "{\"a\": \"b // c\"}");
+
+        let config = JsonCConfig::new()
+            .allow_single_quoted_strings(true)
+            .allow_unquoted_unicode_keys(true)
+            .on_duplicate_key(DuplicateKeyPolicy::Error);
+        // Byte offsets are relative to the input, not the quoted input
+        let json = jsonc_to_json_with_config("{ø: 'a', 'b': 1, ø: 2}", &config);
+        assert_eq!(json, Err(JsonCError::DuplicateKey { byte_offset: 18 }));
+
+        let json = jsonc_to_json_with_config("{a: 1, a: 2}", &config);
+        assert_eq!(json, Err(JsonCError::DuplicateKey { byte_offset: 7 }));
+
+        let json = jsonc_to_json_with_config("{'\\'a': 1, \"'a\": 2}", &config);
+        assert_eq!(json, Err(JsonCError::DuplicateKey { byte_offset: 11 }));
+    }
+
+    #[test]
+    fn test_duplicate_keys() {
+        let jsonc = "{\"a\": 1, // First\n\"b\": [2,], \"a\": 3, // Last\n}";
+
+        let config = JsonCConfig::new();
+        assert_eq!(
+            jsonc_to_json_with_config(jsonc, &config).unwrap(),
+            jsonc_to_json(jsonc)
+        );
+
+        let config = JsonCConfig::new().on_duplicate_key(DuplicateKeyPolicy::KeepFirst);
+        let json = jsonc_to_json_with_config(jsonc, &config).unwrap();
+        assert_eq!(json, "{\"a\": 1, \n\"b\": [2] \n}");
+
+        let config = JsonCConfig::new().on_duplicate_key(DuplicateKeyPolicy::KeepLast);
+        let json = jsonc_to_json_with_config(jsonc, &config).unwrap();
+        assert_eq!(json, "{\"b\": [2], \"a\": 3 \n}");
+
+        // Trailing commas are not introduced, even if they are not stripped
+        let config = JsonCConfig::new()
+            .strip(StripFlags::STRIP_LINE_COMMENTS | StripFlags::STRIP_BLOCK_COMMENTS)
+            .on_duplicate_key(DuplicateKeyPolicy::KeepFirst);
+        let json = jsonc_to_json_with_config("{\"a\": 1, \"a\": 2}", &config).unwrap();
+        assert_eq!(json, "{\"a\": 1}");
+
+        let config = JsonCConfig::new().on_duplicate_key(DuplicateKeyPolicy::Error);
+        assert_eq!(
+            jsonc_to_json_with_config(jsonc, &config),
+            Err(JsonCError::DuplicateKey { byte_offset: 29 })
+        );
+        let json = jsonc_to_json_with_config("[{\"a\": 1}, {\"a\": 2}]", &config);
+        assert!(matches!(json, Ok(Cow::Borrowed(_))));
+    }
+}
@@ -0,0 +1,130 @@
+use std::io::{self, Write};
+
+use flate2::write::DeflateEncoder;
+use flate2::Compression;
+
+use crate::stream::StreamStripper;
+
+/// [`Write`] adapter, which converts the written [JSON with Comments]
+/// into [JSON], and compresses it using [`DeflateEncoder`] before
+/// writing it to `W`.
+///
+/// The input is converted chunk by chunk as it is written, i.e. the
+/// output is never buffered as a whole before compressing. Comments,
+/// strings, and trailing commas are allowed to span across writes.
+///
+/// Call [`finish()`] when done writing, as the end of the input
+/// is needed to determine whether a final comma is a trailing comma,
+/// and to write the end of the compressed stream.
+///
+/// _Requires the `flate2` feature._
+///
+/// # Example
+///
+/// ```rust
+/// use std::io::{Read, Write};
+/// use flate2::read::DeflateDecoder;
+/// use flate2::Compression;
+/// use jsonc_to_json::JsonCToJsonDeflateWriter;
+///
+/// let mut writer = JsonCToJsonDeflateWriter::new(Vec::new(), Compression::default());
+/// writer.write_all(b"{\"arr\": [1, 2,/* Comm")?;
+/// writer.write_all(b"ent */ 3, 4,,]}// Line Comment")?;
+/// let compressed = writer.finish()?;
+///
+/// let mut json = String::new();
+/// DeflateDecoder::new(&compressed[..]).read_to_string(&mut json)?;
+/// assert_eq!(json, "{\"arr\": [1, 2, 3, 4]}");
+/// # Ok::<(), std::io::Error>(())
+/// ```
+///
+/// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+/// [JSON]: https://www.json.org/json-en.html
+/// [`DeflateEncoder`]: https://docs.rs/flate2/latest/flate2/write/struct.DeflateEncoder.html
+/// [`finish()`]: Self::finish
+#[derive(Debug)]
+pub struct JsonCToJsonDeflateWriter<W: Write> {
+    encoder: DeflateEncoder<W>,
+    stripper: StreamStripper,
+    /// Output of converting the current write, which is
+    /// reused across writes.
+    buf: Vec<u8>,
+}
+
+impl<W: Write> JsonCToJsonDeflateWriter<W> {
+    /// Creates a new `JsonCToJsonDeflateWriter`, which writes the
+    /// converted and compressed output to `inner`.
+    #[inline]
+    pub fn new(inner: W, level: Compression) -> Self {
+        Self {
+            encoder: DeflateEncoder::new(inner, level),
+            stripper: StreamStripper::new(),
+            buf: Vec::new(),
+        }
+    }
+
+    /// Returns a reference to the underlying writer.
+    #[inline]
+    pub fn get_ref(&self) -> &W {
+        self.encoder.get_ref()
+    }
+
+    /// Converts and compresses the remaining input, and writes the end
+    /// of the compressed stream, returning the underlying writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.buf.clear();
+        self.stripper.finish(&mut self.buf);
+        self.encoder.write_all(&self.buf)?;
+        self.encoder.finish()
+    }
+}
+
+impl<W: Write> Write for JsonCToJsonDeflateWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buf.clear();
+        self.stripper.feed(buf, &mut self.buf);
+        self.encoder.write_all(&self.buf)?;
+        Ok(buf.len())
+    }
+
+    /// Flushes the compressed output.
+    ///
+    /// **Note:** A comma followed only by whitespace is held back until
+    /// the next significant character is written, i.e. it is not
+    /// flushed, as it might be a trailing comma.
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        self.encoder.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use flate2::read::DeflateDecoder;
+
+    use super::*;
+
+    use crate::jsonc_to_json;
+
+    #[test]
+    fn test_deflate_chunked() {
+        let jsonc = "{\"æøå\": [1, /* ø */ \"日本語\",], // Comment\n\"a\": [1 , , 2 ,\n]}";
+        let expected = jsonc_to_json(jsonc);
+
+        for size in 1..8 {
+            let mut writer = JsonCToJsonDeflateWriter::new(Vec::new(), Compression::fast());
+            for chunk in jsonc.as_bytes().chunks(size) {
+                writer.write_all(chunk).unwrap();
+            }
+            let compressed = writer.finish().unwrap();
+
+            let mut json = String::new();
+            DeflateDecoder::new(&compressed[..])
+                .read_to_string(&mut json)
+                .unwrap();
+            assert_eq!(json, expected);
+        }
+    }
+}
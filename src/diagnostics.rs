@@ -0,0 +1,258 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::ops::Range;
+
+use crate::{collect_cow_with, JsonCToJsonIter, Removed, Span};
+
+/// Diagnostics collected during a conversion,
+/// see [`jsonc_to_json_with_diagnostics()`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(PartialEq, Eq, Clone, Default, Debug)]
+pub struct JsonCDiagnostics {
+    /// All removed comments, i.e. both line comments and block
+    /// comments, ordered by their position in the input.
+    pub comments: Vec<CommentSpan>,
+    /// Warnings about suspicious, but still convertible, input.
+    pub warnings: Vec<ConversionWarning>,
+}
+
+/// Byte ranges of a removed comment, see [`JsonCDiagnostics::comments`].
+///
+/// # Example
+///
+/// ```rust
+/// use std::ops::Range;
+/// use jsonc_to_json::jsonc_to_json_with_diagnostics;
+///
+/// let jsonc = "[1, /* Block */ 2] // Line\n";
+///
+/// let (_json, diagnostics) = jsonc_to_json_with_diagnostics(jsonc);
+///
+/// let block = &diagnostics.comments[0];
+/// assert_eq!(&jsonc[Range::from(block.delimiter_range)], "/* Block */");
+/// assert_eq!(&jsonc[Range::from(block.content_range)], " Block ");
+///
+/// let line = &diagnostics.comments[1];
+/// assert_eq!(&jsonc[Range::from(line.delimiter_range)], "// Line");
+/// assert_eq!(&jsonc[Range::from(line.content_range)], " Line");
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct CommentSpan {
+    /// Byte range of the whole comment, including `//`, `/*`, and `*/`.
+    pub delimiter_range: Span,
+    /// Byte range of the text of the comment, excluding `//`, `/*`,
+    /// and `*/`. For line comments the terminating newline is excluded,
+    /// and for unterminated block comments the range extends to the
+    /// end of the input.
+    pub content_range: Span,
+}
+
+impl CommentSpan {
+    fn new(jsonc: &str, range: Range<usize>, removed: &Removed) -> Self {
+        let comment = &jsonc[range.clone()];
+        // `/*/` is an unterminated block comment, and not `/*` followed by `*/`
+        let terminated = (comment.len() >= 4) && comment.ends_with("*/");
+        let end = match removed {
+            Removed::BlockComment if terminated => range.end - 2,
+            _ => range.end,
+        };
+        Self {
+            delimiter_range: range.clone().into(),
+            content_range: Span::new(range.start + 2, end),
+        }
+    }
+}
+
+/// Warning about suspicious input, which does not prevent the conversion.
+///
+/// See [`jsonc_to_json_with_diagnostics()`] for more information.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub enum ConversionWarning {
+    /// Multiple consecutive commas, e.g. `[1,,,2]`, which often indicates a
+    /// bug in whatever generated the input. The commas are allowed to be
+    /// separated by whitespace and comments.
+    ///
+    /// A single trailing comma, e.g. `[1,2,]`, does not produce a warning.
+    MultipleConsecutiveCommas {
+        /// Number of consecutive commas.
+        count: usize,
+        /// Byte offset of the first comma.
+        byte_offset: usize,
+    },
+}
+
+impl fmt::Display for ConversionWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MultipleConsecutiveCommas { count, byte_offset } => {
+                write!(f, "{} consecutive commas at byte {}", count, byte_offset)
+            }
+        }
+    }
+}
+
+/// Same as [`jsonc_to_json()`], but additionally returns [`JsonCDiagnostics`]
+/// about the conversion.
+///
+/// The diagnostics are collected while converting, i.e. without
+/// a separate pass over `jsonc`.
+///
+/// # Example
+///
+/// ```rust
+/// use jsonc_to_json::{jsonc_to_json_with_diagnostics, ConversionWarning, Span};
+///
+/// let jsonc = "[1,,,2, /* Comment */]";
+///
+/// let (json, diagnostics) = jsonc_to_json_with_diagnostics(jsonc);
+/// assert_eq!(json, "[1,2 ]");
+/// assert_eq!(diagnostics.comments[0].delimiter_range, Span::new(8, 21));
+/// assert_eq!(
+///     diagnostics.warnings,
+///     [ConversionWarning::MultipleConsecutiveCommas {
+///         count: 3,
+///         byte_offset: 2,
+///     }]
+/// );
+/// ```
+///
+/// [`jsonc_to_json()`]: crate::jsonc_to_json
+#[must_use = "this returns the converted JSON; dropping it does nothing"]
+pub fn jsonc_to_json_with_diagnostics(jsonc: &str) -> (Cow<'_, str>, JsonCDiagnostics) {
+    let mut diagnostics = JsonCDiagnostics::default();
+
+    // The current run of consecutive commas, i.e. the byte offset of the
+    // first comma, the number of commas, and the start of the last comma
+    let mut run: Option<(usize, usize, usize)> = None;
+
+    let mut iter = JsonCToJsonIter::new(jsonc);
+    let mut on_removed = |removed, range: Range<usize>| match removed {
+        Removed::LineComment | Removed::BlockComment => {
+            let comment = CommentSpan::new(jsonc, range, &removed);
+            diagnostics.comments.push(comment);
+        }
+        Removed::TrailingComma { next_comma } => {
+            run = match run.take() {
+                // The comma continues the current run
+                Some((first, count, last)) if last == range.start => match next_comma {
+                    Some(next_comma) => Some((first, count + 1, next_comma)),
+                    None => {
+                        diagnostics.warnings.push(consecutive_commas(count, first));
+                        None
+                    }
+                },
+                prev => {
+                    if let Some((first, count, _last)) = prev {
+                        diagnostics.warnings.push(consecutive_commas(count, first));
+                    }
+                    next_comma.map(|next_comma| (range.start, 2, next_comma))
+                }
+            };
+        }
+    };
+    let json = collect_cow_with(jsonc.len(), || iter.next_with(&mut on_removed));
+
+    if let Some((first, count, _last)) = run {
+        diagnostics.warnings.push(consecutive_commas(count, first));
+    }
+
+    (json, diagnostics)
+}
+
+#[inline]
+fn consecutive_commas(count: usize, byte_offset: usize) -> ConversionWarning {
+    ConversionWarning::MultipleConsecutiveCommas { count, byte_offset }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::jsonc_to_json;
+
+    fn warnings(jsonc: &str) -> Vec<ConversionWarning> {
+        let (json, diagnostics) = jsonc_to_json_with_diagnostics(jsonc);
+        assert_eq!(json, jsonc_to_json(jsonc));
+        diagnostics.warnings
+    }
+
+    #[test]
+    fn test_single_trailing_comma() {
+        assert_eq!(warnings("[1,2,]"), []);
+        assert_eq!(warnings("{\"a\": [1,], \"b\": {\"c\": 2,},}"), []);
+    }
+
+    #[test]
+    fn test_consecutive_commas() {
+        assert_eq!(warnings("[1,,2]"), [consecutive_commas(2, 2)]);
+        assert_eq!(warnings("[1,,,2]"), [consecutive_commas(3, 2)]);
+        assert_eq!(warnings("[1,,,]"), [consecutive_commas(3, 2)]);
+        assert_eq!(
+            warnings("[1, ,/**/, // c\n ,2]"),
+            [consecutive_commas(4, 2)]
+        );
+        assert_eq!(
+            warnings("[1,,2,,,3,]"),
+            [consecutive_commas(2, 2), consecutive_commas(3, 5)]
+        );
+        assert_eq!(warnings("[1,,"), [consecutive_commas(2, 2)]);
+    }
+
+    #[test]
+    fn test_comment_spans() {
+        let jsonc = "[1, /**/ /*/ // a\r\n//\n2] /* b";
+        let (_json, diagnostics) = jsonc_to_json_with_diagnostics(jsonc);
+
+        let comments = diagnostics
+            .comments
+            .iter()
+            .map(|comment| {
+                (
+                    &jsonc[Range::from(comment.delimiter_range)],
+                    &jsonc[Range::from(comment.content_range)],
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            comments,
+            [
+                ("/**/", ""),
+                ("/*/ // a\r\n//\n2] /* b", "/ // a\r\n//\n2] /* b"),
+            ]
+        );
+
+        let jsonc = "[1, // a\r\n//\n2]";
+        let (_json, diagnostics) = jsonc_to_json_with_diagnostics(jsonc);
+        assert_eq!(
+            diagnostics.comments,
+            [
+                CommentSpan {
+                    delimiter_range: Span::new(4, 8),
+                    content_range: Span::new(6, 8),
+                },
+                CommentSpan {
+                    delimiter_range: Span::new(10, 12),
+                    content_range: Span::new(12, 12),
+                },
+            ]
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serialize_diagnostics() {
+        let (_json, diagnostics) = jsonc_to_json_with_diagnostics("[1,,2] // Comment");
+
+        let json = serde_json::to_string(&diagnostics).unwrap();
+        assert_eq!(
+            json,
+            r#"{"comments":[{"delimiter_range":{"start":7,"end":17},"content_range":{"start":9,"end":17}}],"warnings":[{"MultipleConsecutiveCommas":{"count":2,"byte_offset":2}}]}"#
+        );
+
+        let de = serde_json::from_str::<JsonCDiagnostics>(&json).unwrap();
+        assert_eq!(de, diagnostics);
+    }
+}
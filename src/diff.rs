@@ -0,0 +1,127 @@
+use std::ops::Range;
+
+use crate::{JsonCToJsonIter, Removed};
+
+/// Edit operation turning [JSON with Comments] into [JSON],
+/// see [`jsonc_to_json_diff()`].
+///
+/// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+/// [JSON]: https://www.json.org/json-en.html
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub enum Edit {
+    /// Delete the byte `range`, e.g. a comment.
+    DeleteRange {
+        /// Byte range to delete.
+        range: Range<usize>,
+        /// What is being deleted.
+        kind: EditKind,
+    },
+    /// Delete the single byte at the byte offset, i.e. a trailing comma.
+    DeleteChar(usize),
+}
+
+impl Edit {
+    /// Returns what is being deleted.
+    #[must_use]
+    #[inline]
+    pub fn kind(&self) -> EditKind {
+        match self {
+            Self::DeleteRange { kind, .. } => *kind,
+            Self::DeleteChar(_) => EditKind::TrailingComma,
+        }
+    }
+
+    /// Returns the byte range being deleted.
+    #[must_use]
+    #[inline]
+    pub fn range(&self) -> Range<usize> {
+        match self {
+            Self::DeleteRange { range, .. } => range.clone(),
+            Self::DeleteChar(byte_offset) => *byte_offset..(byte_offset + 1),
+        }
+    }
+}
+
+/// Kind of [`Edit`].
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum EditKind {
+    /// Line comment, e.g. `// Line Comment`.
+    LineComment,
+    /// Block comment, e.g. `/* Block Comment */`.
+    BlockComment,
+    /// Trailing comma, e.g. `[1,2,3,]`.
+    TrailingComma,
+}
+
+/// Returns the edits [`jsonc_to_json()`] performs, ordered by
+/// their position in `jsonc`.
+///
+/// Comments are represented as [`Edit::DeleteRange`], and trailing
+/// commas as [`Edit::DeleteChar`]. Applying all edits to `jsonc`
+/// results in the same output as [`jsonc_to_json()`].
+///
+/// # Example
+///
+/// ```rust
+/// use jsonc_to_json::{jsonc_to_json_diff, Edit, EditKind};
+///
+/// let jsonc = "[1, 2, /* Comment */ 3,] // Line Comment";
+///
+/// let edits = jsonc_to_json_diff(jsonc);
+/// assert_eq!(
+///     edits,
+///     [
+///         Edit::DeleteRange { range: 7..20, kind: EditKind::BlockComment },
+///         Edit::DeleteChar(22),
+///         Edit::DeleteRange { range: 25..40, kind: EditKind::LineComment },
+///     ]
+/// );
+/// ```
+///
+/// [`jsonc_to_json()`]: crate::jsonc_to_json
+#[must_use]
+pub fn jsonc_to_json_diff(jsonc: &str) -> Vec<Edit> {
+    let mut edits = Vec::new();
+
+    let mut iter = JsonCToJsonIter::new(jsonc);
+    let mut on_removed = |removed, range: Range<usize>| {
+        let edit = match removed {
+            Removed::LineComment => Edit::DeleteRange {
+                range,
+                kind: EditKind::LineComment,
+            },
+            Removed::BlockComment => Edit::DeleteRange {
+                range,
+                kind: EditKind::BlockComment,
+            },
+            Removed::TrailingComma { .. } => Edit::DeleteChar(range.start),
+        };
+        edits.push(edit);
+    };
+    while iter.next_with(&mut on_removed).is_some() {}
+
+    edits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::jsonc_to_json;
+
+    #[test]
+    fn test_apply_diff() {
+        let jsonc = "// A\n{\"a\": [1,,2,], /* B */ /* C */ \"b\": {,},} // D";
+
+        let edits = jsonc_to_json_diff(jsonc);
+        assert!(edits
+            .windows(2)
+            .all(|w| w[0].range().end <= w[1].range().start));
+
+        let mut json = jsonc.to_owned();
+        for edit in edits.iter().rev() {
+            json.replace_range(edit.range(), "");
+        }
+        assert_eq!(json, jsonc_to_json(jsonc));
+    }
+}
@@ -0,0 +1,117 @@
+use std::convert::Infallible;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{jsonc_to_json, JsonCToJsonIter};
+
+/// Owned [JSON], converted from [JSON with Comments] using
+/// [`jsonc_to_json()`].
+///
+/// Unlike [`JsonCToJsonIter`], `JsonCDoc` is not tied to the lifetime
+/// of the input, and can be stored without also storing the input.
+///
+/// **Warning:** The conversion is infallible and does not validate the
+/// input. See [`jsonc_to_json()`] for more information, or
+/// [`ValidJson`] for a validated alternative.
+///
+/// # Example
+///
+/// ```rust
+/// use jsonc_to_json::JsonCDoc;
+///
+/// let jsonc = "{\"arr\": [1, 2,/* Comment */ 3, 4,]}// Line Comment";
+///
+/// let doc = jsonc.parse::<JsonCDoc>().unwrap();
+/// assert_eq!(doc.as_str(), "{\"arr\": [1, 2, 3, 4]}");
+/// ```
+///
+/// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+/// [JSON]: https://www.json.org/json-en.html
+/// [`ValidJson`]: crate::ValidJson
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct JsonCDoc(String);
+
+impl JsonCDoc {
+    /// Converts `jsonc` using [`jsonc_to_json()`], and
+    /// stores the resulting [JSON].
+    ///
+    /// [JSON]: https://www.json.org/json-en.html
+    #[must_use]
+    #[inline]
+    pub fn new(jsonc: &str) -> Self {
+        Self(jsonc_to_json(jsonc).into_owned())
+    }
+
+    /// Returns the converted [JSON] as a string slice.
+    ///
+    /// [JSON]: https://www.json.org/json-en.html
+    #[must_use]
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns a [`JsonCToJsonIter`] borrowing the converted [JSON].
+    ///
+    /// Since the [JSON] has already been converted, the iterator usually yields
+    /// the whole [JSON] as a single string slice, unless it is empty.
+    ///
+    /// [JSON]: https://www.json.org/json-en.html
+    #[inline]
+    pub fn iter(&self) -> JsonCToJsonIter<'_> {
+        JsonCToJsonIter::new(&self.0)
+    }
+
+    /// Returns the inner [`String`].
+    #[must_use]
+    #[inline]
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+impl FromStr for JsonCDoc {
+    type Err = Infallible;
+
+    /// See [`JsonCDoc::new()`] for more information.
+    #[inline]
+    fn from_str(jsonc: &str) -> Result<Self, Self::Err> {
+        Ok(Self::new(jsonc))
+    }
+}
+
+impl AsRef<str> for JsonCDoc {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for JsonCDoc {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<JsonCDoc> for String {
+    #[inline]
+    fn from(doc: JsonCDoc) -> Self {
+        doc.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_doc_iter() {
+        let doc = JsonCDoc::new("[1, /* 2 */ 3,] // Comment");
+        assert_eq!(doc.iter().collect::<Vec<_>>(), ["[1,  3] "]);
+        assert_eq!(doc.to_string(), "[1,  3] ");
+
+        let doc = "// Comment".parse::<JsonCDoc>().unwrap();
+        assert_eq!(doc.iter().next(), None);
+    }
+}
@@ -0,0 +1,253 @@
+use std::collections::HashMap;
+use std::ops::Range;
+
+use any_lexer::JsonCToken;
+
+use crate::tokens::JsonCTokens;
+use crate::JsonCError;
+
+/// Specifies how duplicate keys in objects are handled,
+/// see [`JsonCConfig::on_duplicate_key()`].
+///
+/// Keys are compared as written, i.e. escape sequences are not decoded,
+/// e.g. `"a"` and `"\u0061"` are not considered duplicates.
+///
+/// [`JsonCConfig::on_duplicate_key()`]: crate::JsonCConfig::on_duplicate_key
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Default, Debug)]
+pub enum DuplicateKeyPolicy {
+    /// Duplicate keys are left as is.
+    #[default]
+    Allow,
+    /// Return [`JsonCError::DuplicateKey`] for the first duplicate key.
+    Error,
+    /// Remove all but the first occurrence of each key.
+    KeepFirst,
+    /// Remove all but the last occurrence of each key.
+    KeepLast,
+}
+
+/// Object member, i.e. a key and its value.
+struct Member<'jsonc> {
+    key: &'jsonc str,
+    key_start: usize,
+    value_end: usize,
+}
+
+struct Object<'jsonc> {
+    members: Vec<Member<'jsonc>>,
+    /// Whether the next string is a key, i.e. after `{` or `,`.
+    expect_key: bool,
+}
+
+enum Container<'jsonc> {
+    Object(Object<'jsonc>),
+    Array,
+}
+
+/// Returns the byte ranges of `tokens`, which must be removed to remove
+/// the duplicate keys, and their values, according to `policy`.
+///
+/// The returned ranges are sorted and do not overlap.
+pub(crate) fn duplicate_key_removals(
+    tokens: JsonCTokens<'_>,
+    policy: DuplicateKeyPolicy,
+) -> Result<Vec<Range<usize>>, JsonCError> {
+    if policy == DuplicateKeyPolicy::Allow {
+        return Ok(Vec::new());
+    }
+
+    let mut removals = Vec::new();
+    let mut stack: Vec<Container<'_>> = Vec::new();
+    // End of the last token, which is not whitespace or a comment
+    let mut last_end = 0;
+
+    for (tok, span) in tokens {
+        let s = span.as_str();
+        match tok {
+            JsonCToken::Space | JsonCToken::LineComment | JsonCToken::BlockComment => continue,
+            JsonCToken::String => {
+                if let Some(Container::Object(obj)) = stack.last_mut() {
+                    if obj.expect_key {
+                        obj.expect_key = false;
+                        obj.members.push(Member {
+                            key: s,
+                            key_start: span.start(),
+                            value_end: span.end(),
+                        });
+                    }
+                }
+            }
+            JsonCToken::Punct if s == "," => {
+                if let Some(Container::Object(obj)) = stack.last_mut() {
+                    end_value(obj, last_end);
+                    obj.expect_key = true;
+                }
+            }
+            JsonCToken::Delim if s == "{" => {
+                stack.push(Container::Object(Object {
+                    members: Vec::new(),
+                    expect_key: true,
+                }));
+            }
+            JsonCToken::Delim if s == "[" => stack.push(Container::Array),
+            JsonCToken::Delim => match stack.pop() {
+                Some(Container::Object(mut obj)) => {
+                    end_value(&mut obj, last_end);
+                    object_removals(&obj.members, policy, &mut removals)?;
+                }
+                Some(Container::Array) | None => {}
+            },
+            _ => {}
+        }
+        last_end = span.end();
+    }
+
+    // Unterminated objects
+    while let Some(container) = stack.pop() {
+        if let Container::Object(mut obj) = container {
+            end_value(&mut obj, last_end);
+            object_removals(&obj.members, policy, &mut removals)?;
+        }
+    }
+
+    Ok(merge_ranges(removals))
+}
+
+/// Ends the value of the last member of `obj` at `end`,
+/// unless it has already been ended by a `,`.
+fn end_value(obj: &mut Object<'_>, end: usize) {
+    if !obj.expect_key {
+        if let Some(member) = obj.members.last_mut() {
+            member.value_end = end;
+        }
+    }
+}
+
+fn object_removals(
+    members: &[Member<'_>],
+    policy: DuplicateKeyPolicy,
+    removals: &mut Vec<Range<usize>>,
+) -> Result<(), JsonCError> {
+    // Index of the occurrence of each key, which is kept
+    let mut kept = HashMap::with_capacity(members.len());
+    for (i, member) in members.iter().enumerate() {
+        match (kept.insert(member.key, i), policy) {
+            (None, _) | (_, DuplicateKeyPolicy::Allow | DuplicateKeyPolicy::KeepLast) => {}
+            (Some(_), DuplicateKeyPolicy::Error) => {
+                return Err(JsonCError::DuplicateKey {
+                    byte_offset: member.key_start,
+                });
+            }
+            (Some(first), DuplicateKeyPolicy::KeepFirst) => {
+                kept.insert(member.key, first);
+            }
+        }
+    }
+
+    let is_kept = |i: usize| kept.get(members[i].key) == Some(&i);
+    for (i, member) in members.iter().enumerate() {
+        if is_kept(i) {
+            continue;
+        }
+
+        // Remove up to the next key, i.e. including the comma after the
+        // value. The last member is instead removed from the end of the
+        // last kept value before it, i.e. including the comma before it
+        let range = match members.get(i + 1) {
+            Some(next) => member.key_start..next.key_start,
+            None => match (0..i).rev().find(|&j| is_kept(j)) {
+                Some(j) => members[j].value_end..member.value_end,
+                None => members[0].key_start..member.value_end,
+            },
+        };
+        removals.push(range);
+    }
+
+    Ok(())
+}
+
+fn merge_ranges(mut ranges: Vec<Range<usize>>) -> Vec<Range<usize>> {
+    ranges.sort_unstable_by_key(|range| range.start);
+
+    let mut merged: Vec<Range<usize>> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn remove_duplicates(jsonc: &str, policy: DuplicateKeyPolicy) -> Result<String, JsonCError> {
+        let removals = duplicate_key_removals(JsonCTokens::new(jsonc), policy)?;
+
+        let mut s = String::new();
+        let mut end = 0;
+        for range in removals {
+            s.push_str(&jsonc[end..range.start]);
+            end = range.end;
+        }
+        s.push_str(&jsonc[end..]);
+        Ok(s)
+    }
+
+    #[test]
+    fn test_keep_first_last() {
+        use DuplicateKeyPolicy::*;
+
+        let cases = [
+            ("{}", "{}", "{}"),
+            ("{\"a\": 1, \"a\": 2}", "{\"a\": 1}", "{\"a\": 2}"),
+            (
+                "{\"a\": 1, \"b\": 2, \"a\": 3, \"a\": 4}",
+                "{\"a\": 1, \"b\": 2}",
+                "{\"b\": 2, \"a\": 4}",
+            ),
+            (
+                "{\"a\": {\"b\": 1, \"b\": [2]}, /* prod */ \"a\": {\"b\": 3,},}",
+                "{\"a\": {\"b\": 1},}",
+                "{\"a\": {\"b\": 3,},}",
+            ),
+            (
+                "[{\"a\": 1, \"a\": 2}, {\"a\": 3}]",
+                "[{\"a\": 1}, {\"a\": 3}]",
+                "[{\"a\": 2}, {\"a\": 3}]",
+            ),
+            ("{\"a\": 1, \"a\": 2", "{\"a\": 1", "{\"a\": 2"),
+            (
+                "{\"a\": 1, \"\\u0061\": 2}",
+                "{\"a\": 1, \"\\u0061\": 2}",
+                "{\"a\": 1, \"\\u0061\": 2}",
+            ),
+        ];
+        for (jsonc, first, last) in cases {
+            assert_eq!(remove_duplicates(jsonc, Allow).unwrap(), jsonc);
+            assert_eq!(
+                remove_duplicates(jsonc, KeepFirst).unwrap(),
+                first,
+                "{:?}",
+                jsonc
+            );
+            assert_eq!(
+                remove_duplicates(jsonc, KeepLast).unwrap(),
+                last,
+                "{:?}",
+                jsonc
+            );
+        }
+    }
+
+    #[test]
+    fn test_duplicate_key_error() {
+        let jsonc = "{\"a\": {\"b\": 1, \"c\": 2, \"b\": 3}, \"a\": 4}";
+        assert_eq!(
+            remove_duplicates(jsonc, DuplicateKeyPolicy::Error),
+            Err(JsonCError::DuplicateKey { byte_offset: 23 })
+        );
+    }
+}
@@ -0,0 +1,174 @@
+use std::error;
+use std::fmt;
+
+/// Error returned when [JSON with Comments] is invalid, see e.g.
+/// [`jsonc_to_json_strict()`].
+///
+/// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+/// [`jsonc_to_json_strict()`]: crate::jsonc_to_json_strict
+#[non_exhaustive]
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub enum JsonCError {
+    /// Block comment without a closing `*/`.
+    UnterminatedBlockComment {
+        /// Byte offset of the start of the block comment.
+        byte_offset: usize,
+    },
+    /// String without a closing `"`.
+    UnterminatedString {
+        /// Byte offset of the start of the string.
+        byte_offset: usize,
+    },
+    /// Invalid escape sequence inside a string, e.g. `"\x"`.
+    InvalidEscape {
+        /// Byte offset of the `\` starting the escape sequence.
+        byte_offset: usize,
+    },
+    /// Control character inside a string, which must be escaped, e.g. a newline.
+    UnescapedControlCharacter {
+        /// Byte offset of the control character.
+        byte_offset: usize,
+    },
+    /// Token which is not allowed at its position, e.g. a missing comma,
+    /// or an invalid token such as `01` or `undefined`.
+    UnexpectedToken {
+        /// Byte offset of the start of the token.
+        byte_offset: usize,
+    },
+    /// Valid [JSON] containing a value that cannot be represented,
+    /// e.g. a number that is out of range, or arrays and objects
+    /// nested too deeply.
+    ///
+    /// [JSON]: https://www.json.org/json-en.html
+    Unrepresentable {
+        /// Byte offset of the value, or near the value.
+        byte_offset: usize,
+    },
+    /// Reference to a variable, which is not defined,
+    /// see [`jsonc_expand_vars_with()`].
+    ///
+    /// [`jsonc_expand_vars_with()`]: crate::jsonc_expand_vars_with
+    UnknownVariable {
+        /// Byte offset of the `$` starting the variable reference.
+        byte_offset: usize,
+    },
+    /// The input is longer than the configured limit,
+    /// see [`JsonCConfig::max_input_bytes()`].
+    ///
+    /// [`JsonCConfig::max_input_bytes()`]: crate::JsonCConfig::max_input_bytes
+    InputTooLarge {
+        /// Maximum length of the input in bytes.
+        limit: usize,
+        /// Actual length of the input in bytes.
+        actual: usize,
+    },
+    /// The input ended before the [JSON] value was complete.
+    ///
+    /// [JSON]: https://www.json.org/json-en.html
+    UnexpectedEof {
+        /// Byte offset of the end of the input.
+        byte_offset: usize,
+    },
+    /// The input is not valid UTF-8,
+    /// see [`jsonc_to_json_null_delimited()`].
+    ///
+    /// [`jsonc_to_json_null_delimited()`]: crate::jsonc_to_json_null_delimited
+    InvalidUtf8 {
+        /// Byte offset of the first invalid byte.
+        byte_offset: usize,
+    },
+    /// Content after the complete root value, other than whitespace
+    /// and comments, e.g. `{"a": 1} garbage`.
+    ///
+    /// Only returned by strict conversion and validation,
+    /// see [`jsonc_to_json_strict()`].
+    ///
+    /// [`jsonc_to_json_strict()`]: crate::jsonc_to_json_strict
+    TrailingContent {
+        /// Byte offset of the first token after the root value.
+        byte_offset: usize,
+    },
+    /// Duplicate key in an object, returned when using
+    /// [`DuplicateKeyPolicy::Error`].
+    ///
+    /// [`DuplicateKeyPolicy::Error`]: crate::DuplicateKeyPolicy::Error
+    DuplicateKey {
+        /// Byte offset of the second occurrence of the key.
+        byte_offset: usize,
+    },
+}
+
+impl JsonCError {
+    /// Returns the error with its byte offset mapped by `f`, e.g.
+    /// from an intermediate rewritten input back into the original input.
+    pub(crate) fn map_byte_offset(mut self, f: impl FnOnce(usize) -> usize) -> Self {
+        match &mut self {
+            Self::UnterminatedBlockComment { byte_offset }
+            | Self::UnterminatedString { byte_offset }
+            | Self::InvalidEscape { byte_offset }
+            | Self::UnescapedControlCharacter { byte_offset }
+            | Self::UnexpectedToken { byte_offset }
+            | Self::Unrepresentable { byte_offset }
+            | Self::UnknownVariable { byte_offset }
+            | Self::UnexpectedEof { byte_offset }
+            | Self::InvalidUtf8 { byte_offset }
+            | Self::TrailingContent { byte_offset }
+            | Self::DuplicateKey { byte_offset } => *byte_offset = f(*byte_offset),
+            Self::InputTooLarge { .. } => {}
+        }
+        self
+    }
+}
+
+impl fmt::Display for JsonCError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnterminatedBlockComment { byte_offset } => {
+                write!(f, "unterminated block comment at byte {}", byte_offset)
+            }
+            Self::UnterminatedString { byte_offset } => {
+                write!(f, "unterminated string at byte {}", byte_offset)
+            }
+            Self::InvalidEscape { byte_offset } => {
+                write!(f, "invalid escape sequence at byte {}", byte_offset)
+            }
+            Self::UnescapedControlCharacter { byte_offset } => {
+                write!(f, "unescaped control character at byte {}", byte_offset)
+            }
+            Self::UnexpectedToken { byte_offset } => {
+                write!(f, "unexpected token at byte {}", byte_offset)
+            }
+            Self::Unrepresentable { byte_offset } => {
+                write!(f, "unrepresentable value at byte {}", byte_offset)
+            }
+            Self::UnknownVariable { byte_offset } => {
+                write!(f, "unknown variable at byte {}", byte_offset)
+            }
+            Self::InputTooLarge { limit, actual } => {
+                write!(
+                    f,
+                    "input of {} bytes exceeds limit of {} bytes",
+                    actual, limit
+                )
+            }
+            Self::UnexpectedEof { byte_offset } => {
+                write!(f, "unexpected end of input at byte {}", byte_offset)
+            }
+            Self::InvalidUtf8 { byte_offset } => {
+                write!(f, "invalid UTF-8 at byte {}", byte_offset)
+            }
+            Self::TrailingContent { byte_offset } => {
+                write!(
+                    f,
+                    "trailing content after root value at byte {}",
+                    byte_offset
+                )
+            }
+            Self::DuplicateKey { byte_offset } => {
+                write!(f, "duplicate key at byte {}", byte_offset)
+            }
+        }
+    }
+}
+
+impl error::Error for JsonCError {}
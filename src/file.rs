@@ -0,0 +1,289 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::jsonc_to_json;
+use crate::stream::StreamStripper;
+
+/// Reads the [JSON with Comments] file at `path`, converts it into
+/// [JSON] using [`jsonc_to_json()`], and overwrites the file with
+/// the output.
+///
+/// The output is written to a new temporary file in the same directory,
+/// which is synced to disk and then renamed over `path`, i.e. on POSIX
+/// systems the file is replaced atomically, and is never observed partially
+/// written, even after a crash. The permissions of the original file
+/// are preserved.
+///
+/// If nothing is removed, then the file is not written to,
+/// i.e. its modification time is left unchanged.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use jsonc_to_json::jsonc_to_json_file_in_place;
+///
+/// jsonc_to_json_file_in_place("config.json")?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+///
+/// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+/// [JSON]: https://www.json.org/json-en.html
+/// [`jsonc_to_json()`]: crate::jsonc_to_json
+pub fn jsonc_to_json_file_in_place(path: impl AsRef<Path>) -> io::Result<()> {
+    let path = path.as_ref();
+
+    let jsonc = fs::read_to_string(path)?;
+    let json = jsonc_to_json(&jsonc);
+    if json.len() == jsonc.len() {
+        return Ok(());
+    }
+
+    let permissions = fs::metadata(path)?.permissions();
+    write_atomic(path, json.as_bytes(), Some(permissions))
+}
+
+/// Reads the [JSON with Comments] file at `src`, converts it into
+/// [JSON] using [`jsonc_to_json()`], and writes the output to `dst`.
+///
+/// Like [`jsonc_to_json_file_in_place()`], the output is written to a
+/// temporary file, which is then renamed to `dst`. So `src` and `dst`
+/// are allowed to be the same path.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use jsonc_to_json::jsonc_to_json_file;
+///
+/// jsonc_to_json_file("config.jsonc", "config.json")?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+///
+/// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+/// [JSON]: https://www.json.org/json-en.html
+/// [`jsonc_to_json()`]: crate::jsonc_to_json
+pub fn jsonc_to_json_file(src: impl AsRef<Path>, dst: impl AsRef<Path>) -> io::Result<()> {
+    let jsonc = fs::read_to_string(src)?;
+    let json = jsonc_to_json(&jsonc);
+    write_atomic(dst.as_ref(), json.as_bytes(), None)
+}
+
+/// Reads the [JSON with Comments] file at `path` in windows of at most
+/// `chunk_size` bytes, and writes the converted [JSON] to `out`.
+///
+/// Unlike [`jsonc_to_json_file()`], the whole file is never read into
+/// memory, i.e. this can be used for files larger than the available
+/// memory. Strings, comments, and trailing commas are allowed to span
+/// across windows, and the output is the same as [`jsonc_to_json()`].
+///
+/// **Note:** A comma followed by whitespace is held back until the next
+/// significant byte is read, i.e. a huge run of whitespace after a comma
+/// is buffered regardless of `chunk_size`. A `chunk_size` of `0` is
+/// treated as `1`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use std::fs::File;
+/// use std::io::BufWriter;
+/// use jsonc_to_json::jsonc_to_json_chunked;
+///
+/// let mut out = BufWriter::new(File::create("large.json")?);
+/// jsonc_to_json_chunked("large.jsonc", 64 * 1024, &mut out)?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+///
+/// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+/// [JSON]: https://www.json.org/json-en.html
+/// [`jsonc_to_json()`]: crate::jsonc_to_json
+pub fn jsonc_to_json_chunked(
+    path: impl AsRef<Path>,
+    chunk_size: usize,
+    out: &mut dyn Write,
+) -> io::Result<()> {
+    let mut file = File::open(path)?;
+
+    let mut stripper = StreamStripper::new();
+    let mut chunk = vec![0; chunk_size.max(1)];
+    let mut json = Vec::with_capacity(chunk.len());
+    loop {
+        let n = match file.read(&mut chunk) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        };
+
+        json.clear();
+        stripper.feed(&chunk[..n], &mut json);
+        out.write_all(&json)?;
+    }
+
+    json.clear();
+    stripper.finish(&mut json);
+    out.write_all(&json)?;
+    out.flush()
+}
+
+/// Writes `contents` to a new temporary file next to `path`, syncs
+/// it to disk, and then renames it to `path`.
+fn write_atomic(
+    path: &Path,
+    contents: &[u8],
+    permissions: Option<fs::Permissions>,
+) -> io::Result<()> {
+    let (tmp, mut file) = create_tmp(path)?;
+
+    let res = file
+        .write_all(contents)
+        .and_then(|()| match permissions {
+            Some(permissions) => file.set_permissions(permissions),
+            None => Ok(()),
+        })
+        .and_then(|()| file.sync_all());
+    drop(file);
+
+    if let Err(err) = res.and_then(|()| fs::rename(&tmp, path)) {
+        let _ = fs::remove_file(&tmp);
+        return Err(err);
+    }
+
+    sync_parent_dir(path)
+}
+
+/// Counter making temporary file names unique within the process.
+static TMP_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Creates a new temporary file next to `path`, named
+/// `<file>.<pid>.<counter>.tmp`. Existing files are never
+/// truncated, instead the next name is tried.
+fn create_tmp(path: &Path) -> io::Result<(PathBuf, File)> {
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "path does not name a file"))?;
+
+    loop {
+        let mut tmp_name = file_name.to_os_string();
+        tmp_name.push(format!(
+            ".{}.{}.tmp",
+            process::id(),
+            TMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        let tmp = path.with_file_name(tmp_name);
+
+        match OpenOptions::new().write(true).create_new(true).open(&tmp) {
+            Ok(file) => return Ok((tmp, file)),
+            // E.g. left behind by a previous process with the same pid
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// Syncs the directory containing `path`, such that a rename
+/// into it is persisted.
+#[cfg(unix)]
+fn sync_parent_dir(path: &Path) -> io::Result<()> {
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    File::open(dir)?.sync_all()
+}
+
+#[cfg(not(unix))]
+#[inline]
+fn sync_parent_dir(_path: &Path) -> io::Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    #[test]
+    fn test_file_in_place() {
+        let dir = env::temp_dir().join("jsonc_to_json_test_file_in_place");
+        fs::create_dir_all(&dir).unwrap();
+
+        let src = dir.join("config.jsonc");
+        let dst = dir.join("config.json");
+        fs::write(&src, "{\"a\": [1, 2,], // Comment\n}").unwrap();
+
+        jsonc_to_json_file(&src, &dst).unwrap();
+        assert_eq!(fs::read_to_string(&dst).unwrap(), "{\"a\": [1, 2] \n}");
+
+        jsonc_to_json_file_in_place(&src).unwrap();
+        assert_eq!(fs::read_to_string(&src).unwrap(), "{\"a\": [1, 2] \n}");
+
+        // Only the converted files are left
+        let mut files = fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect::<Vec<_>>();
+        files.sort();
+        assert_eq!(files, ["config.json", "config.jsonc"]);
+
+        let err = jsonc_to_json_file_in_place(dir.join("missing.jsonc")).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_file_concurrent() {
+        use std::thread;
+
+        let dir = env::temp_dir().join("jsonc_to_json_test_file_concurrent");
+        fs::create_dir_all(&dir).unwrap();
+
+        let src = dir.join("config.jsonc");
+        let dst = dir.join("config.json");
+        fs::write(&src, "[1, /* a */ 2,]").unwrap();
+
+        thread::scope(|scope| {
+            for _ in 0..8 {
+                scope.spawn(|| {
+                    for _ in 0..10 {
+                        jsonc_to_json_file(&src, &dst).unwrap();
+                    }
+                });
+            }
+        });
+        assert_eq!(fs::read_to_string(&dst).unwrap(), "[1,  2]");
+
+        let mut files = fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect::<Vec<_>>();
+        files.sort();
+        assert_eq!(files, ["config.json", "config.jsonc"]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_file_chunked() {
+        let dir = env::temp_dir().join("jsonc_to_json_test_file_chunked");
+        fs::create_dir_all(&dir).unwrap();
+
+        let jsonc = "{\"a/*b*/\": [1, /* æøå */ 2,\n], // 日本語\n\"c\": \"\\\"//\",}";
+        let path = dir.join("large.jsonc");
+        fs::write(&path, jsonc).unwrap();
+
+        for chunk_size in 0..=jsonc.len() + 1 {
+            let mut json = Vec::new();
+            jsonc_to_json_chunked(&path, chunk_size, &mut json).unwrap();
+            assert_eq!(String::from_utf8(json).unwrap(), jsonc_to_json(jsonc));
+        }
+
+        let err = jsonc_to_json_chunked(dir.join("missing.jsonc"), 8, &mut io::sink()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,84 @@
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign};
+
+/// Bitflags specifying which [JSON with Comments] features to strip,
+/// see [`jsonc_to_json_selective()`].
+///
+/// Flags can be combined using `|`, e.g.
+/// `StripFlags::STRIP_LINE_COMMENTS | StripFlags::STRIP_BLOCK_COMMENTS`.
+///
+/// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+/// [`jsonc_to_json_selective()`]: crate::jsonc_to_json_selective
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct StripFlags(u8);
+
+impl StripFlags {
+    /// Strip nothing.
+    pub const NONE: Self = Self(0);
+    /// Strip line comments, e.g. `// Line Comment`.
+    pub const STRIP_LINE_COMMENTS: Self = Self(1 << 0);
+    /// Strip block comments, e.g. `/* Block Comment */`.
+    pub const STRIP_BLOCK_COMMENTS: Self = Self(1 << 1);
+    /// Strip trailing commas, e.g. `[1,2,3,,]` -> `[1,2,3]`.
+    pub const STRIP_TRAILING_COMMAS: Self = Self(1 << 2);
+    /// Strip line comments and block comments.
+    pub const STRIP_COMMENTS: Self =
+        Self(Self::STRIP_LINE_COMMENTS.0 | Self::STRIP_BLOCK_COMMENTS.0);
+    /// Strip everything, i.e. the same as [`jsonc_to_json()`].
+    ///
+    /// [`jsonc_to_json()`]: crate::jsonc_to_json
+    pub const ALL: Self = Self(Self::STRIP_COMMENTS.0 | Self::STRIP_TRAILING_COMMAS.0);
+
+    /// Returns `true` if all flags in `other` are set in `self`.
+    #[must_use]
+    #[inline]
+    pub const fn contains(self, other: Self) -> bool {
+        (self.0 & other.0) == other.0
+    }
+
+    /// Returns `true` if no flags are set.
+    #[must_use]
+    #[inline]
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl Default for StripFlags {
+    /// Returns [`StripFlags::ALL`].
+    #[inline]
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+impl BitOr for StripFlags {
+    type Output = Self;
+
+    #[inline]
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for StripFlags {
+    #[inline]
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl BitAnd for StripFlags {
+    type Output = Self;
+
+    #[inline]
+    fn bitand(self, rhs: Self) -> Self::Output {
+        Self(self.0 & rhs.0)
+    }
+}
+
+impl BitAndAssign for StripFlags {
+    #[inline]
+    fn bitand_assign(&mut self, rhs: Self) {
+        self.0 &= rhs.0;
+    }
+}
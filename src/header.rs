@@ -0,0 +1,172 @@
+use std::borrow::Cow;
+use std::iter;
+
+use crate::{CommentKind, JsonCConfig};
+
+/// Turns [JSON] into [JSON with Comments], by prepending `header_comment`
+/// as a block comment, e.g. for marking a generated config file.
+///
+/// Valid [JSON] is already valid [JSON with Comments], so `json` itself
+/// is returned unchanged. If `header_comment` is `None`, then `json` is
+/// returned as [`Cow::Borrowed`].
+///
+/// If `header_comment` contains `*/`, then line comments are used instead,
+/// as the block comment would otherwise end early.
+///
+/// See [`json_to_jsonc_with_config()`] for using line comments.
+///
+/// # Example
+///
+/// ```rust
+/// use jsonc_to_json::{json_to_jsonc, jsonc_to_json};
+///
+/// let json = "{\"a\": 1}";
+///
+/// let jsonc = json_to_jsonc(json, Some("Generated by example"));
+/// assert_eq!(jsonc, "/* Generated by example */\n{\"a\": 1}");
+///
+/// assert_eq!(jsonc_to_json(&jsonc).trim_start(), json);
+/// ```
+///
+/// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+/// [JSON]: https://www.json.org/json-en.html
+#[must_use = "this returns the converted JSON with Comments; dropping it does nothing"]
+pub fn json_to_jsonc<'json>(json: &'json str, header_comment: Option<&str>) -> Cow<'json, str> {
+    match header_comment {
+        Some(header) => Cow::Owned(with_header(json, header, CommentKind::Block)),
+        None => Cow::Borrowed(json),
+    }
+}
+
+/// Same as [`json_to_jsonc()`], but using the header comment specified
+/// by `config`, see [`JsonCConfig::header_comment()`] and
+/// [`JsonCConfig::header_comment_kind()`].
+///
+/// # Example
+///
+/// ```rust
+/// use jsonc_to_json::{json_to_jsonc_with_config, CommentKind, JsonCConfig};
+///
+/// let config = JsonCConfig::new()
+///     .header_comment("Generated by example\nDo not edit")
+///     .header_comment_kind(CommentKind::Line);
+///
+/// let jsonc = json_to_jsonc_with_config("[1, 2]", &config);
+/// assert_eq!(jsonc, "// Generated by example\n// Do not edit\n[1, 2]");
+/// ```
+#[must_use = "this returns the converted JSON with Comments; dropping it does nothing"]
+pub fn json_to_jsonc_with_config<'json>(json: &'json str, config: &JsonCConfig) -> Cow<'json, str> {
+    match config.header_comment.as_deref() {
+        Some(header) => Cow::Owned(with_header(json, header, config.header_comment_kind)),
+        None => Cow::Borrowed(json),
+    }
+}
+
+fn with_header(json: &str, header: &str, kind: CommentKind) -> String {
+    let kind = match kind {
+        CommentKind::Block if header.contains("*/") => CommentKind::Line,
+        kind => kind,
+    };
+
+    let mut jsonc = String::with_capacity(header.len() + json.len() + 8);
+    match kind {
+        CommentKind::Block => {
+            jsonc.push_str("/* ");
+            jsonc.push_str(header);
+            jsonc.push_str(" */\n");
+        }
+        CommentKind::Line => {
+            for line in comment_lines(header) {
+                if line.is_empty() {
+                    jsonc.push_str("//\n");
+                } else {
+                    jsonc.push_str("// ");
+                    jsonc.push_str(line);
+                    jsonc.push('\n');
+                }
+            }
+        }
+    }
+    jsonc.push_str(json);
+    jsonc
+}
+
+/// Same as [`str::lines()`], but also splits on a lone `\r`, as line
+/// comments end at `\r` too, i.e. each line can be turned into a line
+/// comment.
+pub(crate) fn comment_lines(text: &str) -> impl Iterator<Item = &str> {
+    let mut rest = text;
+    iter::from_fn(move || {
+        if rest.is_empty() {
+            return None;
+        }
+        match rest.find(['\r', '\n']) {
+            Some(i) => {
+                let line = &rest[..i];
+                let len = if rest[i..].starts_with("\r\n") { 2 } else { 1 };
+                rest = &rest[(i + len)..];
+                Some(line)
+            }
+            None => Some(std::mem::take(&mut rest)),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{jsonc_extract_comments, jsonc_to_json, jsonc_to_json_strict};
+
+    #[test]
+    fn test_json_to_jsonc_round_trip() {
+        let json = "{\"a\": [1, 2], \"b\": \"/* c */\"}";
+        assert!(matches!(json_to_jsonc(json, None), Cow::Borrowed(_)));
+
+        let headers = ["Header", "", "a */ b", "Line 1\n\nLine 3\r\n"];
+        for kind in [CommentKind::Block, CommentKind::Line] {
+            for header in headers {
+                let config = JsonCConfig::new()
+                    .header_comment(header)
+                    .header_comment_kind(kind);
+                let jsonc = json_to_jsonc_with_config(json, &config);
+
+                assert_eq!(jsonc_to_json(&jsonc).trim_start(), json, "{:?}", jsonc);
+
+                let comments = jsonc_extract_comments(&jsonc);
+                let text = comments
+                    .iter()
+                    .map(|comment| comment.text.trim())
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                assert_eq!(text, header.trim().replace("\r\n", "\n"), "{:?}", jsonc);
+            }
+        }
+    }
+
+    #[test]
+    fn test_header_lone_carriage_return() {
+        let config = JsonCConfig::new()
+            .header_comment("Generated\rby tool")
+            .header_comment_kind(CommentKind::Line);
+        let jsonc = json_to_jsonc_with_config("[1]", &config);
+        assert_eq!(jsonc, "// Generated\n// by tool\n[1]");
+        assert_eq!(jsonc_to_json_strict(&jsonc).unwrap().trim_start(), "[1]");
+
+        let jsonc = json_to_jsonc("[1]", Some("a */ b\rc"));
+        assert_eq!(jsonc, "// a */ b\n// c\n[1]");
+        assert_eq!(jsonc_to_json_strict(&jsonc).unwrap().trim_start(), "[1]");
+    }
+
+    #[test]
+    fn test_comment_lines() {
+        let lines = |text| comment_lines(text).collect::<Vec<_>>();
+        assert_eq!(lines(""), [""; 0]);
+        assert_eq!(lines("a"), ["a"]);
+        assert_eq!(lines("a\r\nb\rc\nd"), ["a", "b", "c", "d"]);
+        assert_eq!(lines("a\n\r\n\rb\r"), ["a", "", "", "b"]);
+        for text in ["", "a\nb", "a\r\n\nb\n", "\n"] {
+            assert_eq!(lines(text), text.lines().collect::<Vec<_>>());
+        }
+    }
+}
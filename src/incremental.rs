@@ -0,0 +1,290 @@
+use std::ops::Range;
+
+use any_lexer::JsonCToken;
+
+use crate::tokens::JsonCTokens;
+
+/// [JSON with Comments] document, which keeps its converted [JSON] up to
+/// date while being edited, e.g. for use in a language server.
+///
+/// [`apply_edit()`](Self::apply_edit) only re-tokenizes the region
+/// affected by the edit, and patches the stored [JSON] in place, instead
+/// of converting the whole document again.
+///
+/// The output is the same as [`jsonc_to_json()`], given the same input.
+///
+/// # Example
+///
+/// ```rust
+/// use jsonc_to_json::IncrementalJsonC;
+///
+/// let mut doc = IncrementalJsonC::new("[1, /* Comment */ 2,]");
+/// assert_eq!(doc.current_json(), "[1,  2]");
+///
+/// doc.apply_edit(4..17, "3");
+/// assert_eq!(doc.jsonc(), "[1, 3 2,]");
+/// assert_eq!(doc.current_json(), "[1, 3 2]");
+///
+/// doc.apply_edit(5..5, ",");
+/// assert_eq!(doc.current_json(), "[1, 3, 2]");
+/// ```
+///
+/// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+/// [JSON]: https://www.json.org/json-en.html
+/// [`jsonc_to_json()`]: crate::jsonc_to_json
+#[derive(Clone, Debug)]
+pub struct IncrementalJsonC {
+    jsonc: String,
+    tokens: Vec<Token>,
+    json: String,
+}
+
+#[derive(Clone, Debug)]
+struct Token {
+    kind: JsonCToken,
+    range: Range<usize>,
+    /// Whether the token is included in the output.
+    kept: bool,
+}
+
+impl IncrementalJsonC {
+    /// Creates a new `IncrementalJsonC`, and converts all of `jsonc`.
+    #[must_use]
+    pub fn new(jsonc: impl Into<String>) -> Self {
+        let mut doc = Self {
+            jsonc: String::new(),
+            tokens: Vec::new(),
+            json: String::new(),
+        };
+        let jsonc = jsonc.into();
+        doc.apply_edit(0..0, &jsonc);
+        doc
+    }
+
+    /// Returns the current [JSON with Comments].
+    ///
+    /// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+    #[must_use]
+    #[inline]
+    pub fn jsonc(&self) -> &str {
+        &self.jsonc
+    }
+
+    /// Returns the [JSON] converted from the current [JSON with Comments].
+    ///
+    /// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+    /// [JSON]: https://www.json.org/json-en.html
+    #[must_use]
+    #[inline]
+    pub fn current_json(&self) -> &str {
+        &self.json
+    }
+
+    /// Replaces the byte `range` of the [JSON with Comments] with
+    /// `new_text`, and updates the converted [JSON].
+    ///
+    /// Tokens are re-tokenized from the token containing the start of
+    /// `range`, until the tokens line up with the tokens after `range`.
+    /// The byte ranges of all following tokens are shifted, but they
+    /// are not re-tokenized.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start or end of `range` is out of bounds,
+    /// or does not lie on a [`char`] boundary.
+    ///
+    /// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+    /// [JSON]: https://www.json.org/json-en.html
+    pub fn apply_edit(&mut self, range: Range<usize>, new_text: &str) {
+        self.jsonc.replace_range(range.clone(), new_text);
+
+        // Tokens before `first` are unaffected by the edit, i.e. `first`
+        // is the token containing the start of `range`, or ending at it
+        let first = self
+            .tokens
+            .partition_point(|tok| tok.range.start < range.start)
+            .saturating_sub(1);
+        let start = self.tokens.get(first).map_or(0, |tok| tok.range.start);
+
+        // Whether a trailing comma is removed depends on the next
+        // token, so the previous comma must also be reconsidered
+        let prev = self.tokens[..first]
+            .iter()
+            .rposition(|tok| !is_space_or_comment(tok.kind))
+            .filter(|&i| is_comma(&self.jsonc, &self.tokens[i]))
+            .unwrap_or(first);
+
+        let delta = (new_text.len() as isize) - (range.len() as isize);
+        let shift = |i: usize| ((i as isize) + delta) as usize;
+
+        let mut lexer = JsonCTokens::new(&self.jsonc);
+        lexer.set_cursor_pos(start);
+
+        // The old tokens `first..last` are replaced by `new_tokens`
+        let mut last = first;
+        let mut new_tokens = Vec::new();
+        let mut resynced = false;
+        for (kind, span) in lexer {
+            let span_start = span.start();
+            while let Some(tok) = self.tokens.get(last) {
+                if (tok.range.start >= range.end) && (shift(tok.range.start) >= span_start) {
+                    break;
+                }
+                last += 1;
+            }
+
+            // The lexer has no state between tokens, so when a token starts
+            // where an old token after the edit starts, then the remaining
+            // tokens are the same as the old tokens
+            if let Some(tok) = self.tokens.get(last) {
+                if shift(tok.range.start) == span_start {
+                    resynced = true;
+                    break;
+                }
+            }
+
+            new_tokens.push(Token {
+                kind,
+                range: span.range(),
+                kept: true,
+            });
+        }
+        if !resynced {
+            last = self.tokens.len();
+        }
+
+        let json_start = kept_len(&self.tokens[..prev]);
+        let json_end = json_start + kept_len(&self.tokens[prev..last]);
+
+        let new_len = new_tokens.len();
+        self.tokens.splice(first..last, new_tokens);
+        for tok in &mut self.tokens[(first + new_len)..] {
+            tok.range = shift(tok.range.start)..shift(tok.range.end);
+        }
+
+        let mut json = String::new();
+        for i in prev..(first + new_len) {
+            let kept = self.is_kept(i);
+            let tok = &mut self.tokens[i];
+            tok.kept = kept;
+            if kept {
+                json.push_str(&self.jsonc[tok.range.clone()]);
+            }
+        }
+        self.json.replace_range(json_start..json_end, &json);
+    }
+
+    /// Returns `true` if the token at index `i` is included in the output,
+    /// i.e. the same as [`jsonc_to_json()`].
+    ///
+    /// [`jsonc_to_json()`]: crate::jsonc_to_json
+    fn is_kept(&self, i: usize) -> bool {
+        let tok = &self.tokens[i];
+        if is_space_or_comment(tok.kind) {
+            return tok.kind == JsonCToken::Space;
+        }
+        if !is_comma(&self.jsonc, tok) {
+            return true;
+        }
+
+        let next = self.tokens[(i + 1)..]
+            .iter()
+            .find(|tok| !is_space_or_comment(tok.kind));
+        match next {
+            None => false,
+            Some(next) if is_comma(&self.jsonc, next) => false,
+            Some(next) => {
+                (next.kind != JsonCToken::Delim)
+                    || !matches!(&self.jsonc[next.range.clone()], "}" | "]")
+            }
+        }
+    }
+}
+
+#[inline]
+fn is_space_or_comment(kind: JsonCToken) -> bool {
+    matches!(
+        kind,
+        JsonCToken::Space | JsonCToken::LineComment | JsonCToken::BlockComment
+    )
+}
+
+#[inline]
+fn is_comma(jsonc: &str, tok: &Token) -> bool {
+    (tok.kind == JsonCToken::Punct) && (&jsonc[tok.range.clone()] == ",")
+}
+
+#[inline]
+fn kept_len(tokens: &[Token]) -> usize {
+    tokens
+        .iter()
+        .filter(|tok| tok.kept)
+        .map(|tok| tok.range.len())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::jsonc_to_json;
+
+    #[test]
+    fn test_incremental_edits() {
+        let mut doc = IncrementalJsonC::new("");
+        let mut jsonc = String::new();
+
+        // Simple deterministic pseudo-random number generator
+        let mut seed = 0x2545_f491_u32;
+        let mut rand = |n: usize| {
+            seed ^= seed << 13;
+            seed ^= seed >> 17;
+            seed ^= seed << 5;
+            (seed as usize) % n
+        };
+
+        const TEXTS: &[&str] = &[
+            "",
+            "/",
+            "*",
+            "/*",
+            "*/",
+            "//",
+            "\n",
+            " ",
+            ",",
+            ",,",
+            "\"",
+            "\\",
+            "[",
+            "]",
+            "{",
+            "}",
+            "1",
+            "23",
+            "true",
+            "null",
+            ":",
+            "\"a\": 1,",
+            "ø",
+        ];
+
+        for _ in 0..5000 {
+            let mut start = rand(jsonc.len() + 1);
+            while !jsonc.is_char_boundary(start) {
+                start -= 1;
+            }
+            let mut end = start + rand((jsonc.len() - start).min(4) + 1);
+            while !jsonc.is_char_boundary(end) {
+                end += 1;
+            }
+            let text = TEXTS[rand(TEXTS.len())];
+
+            jsonc.replace_range(start..end, text);
+            doc.apply_edit(start..end, text);
+
+            assert_eq!(doc.jsonc(), jsonc);
+            assert_eq!(doc.current_json(), jsonc_to_json(&jsonc), "{:?}", jsonc);
+        }
+    }
+}
@@ -0,0 +1,148 @@
+//! Optional instrumentation of conversions.
+//!
+//! When the `tracing` feature is enabled, then each conversion is wrapped
+//! in a span. When the `log` feature is enabled, then each conversion is
+//! logged. Both features emit a trace event for each removed part.
+//!
+//! When neither feature is enabled, then [`Instrument`] is
+//! zero-sized, and all of its methods are no-ops.
+
+use std::ops::Range;
+
+use crate::Removed;
+
+/// Creates an [`Instrument`] for the conversion function `$name`
+/// converting `$jsonc`.
+#[cfg(feature = "tracing")]
+macro_rules! instrument {
+    ($name:literal, $jsonc:expr) => {
+        $crate::instrument::Instrument::new(
+            $name,
+            $jsonc.len(),
+            tracing::debug_span!(
+                $name,
+                input_len = $jsonc.len(),
+                output_len = tracing::field::Empty,
+                line_comments_removed = tracing::field::Empty,
+                block_comments_removed = tracing::field::Empty,
+                trailing_commas_removed = tracing::field::Empty,
+            ),
+        )
+    };
+}
+
+/// Creates an [`Instrument`] for the conversion function `$name`
+/// converting `$jsonc`.
+#[cfg(not(feature = "tracing"))]
+macro_rules! instrument {
+    ($name:literal, $jsonc:expr) => {
+        $crate::instrument::Instrument::new($name, $jsonc.len())
+    };
+}
+
+pub(crate) use instrument;
+
+/// Emits a trace event to each enabled backend.
+macro_rules! trace {
+    ($($arg:tt)+) => {{
+        #[cfg(feature = "tracing")]
+        tracing::trace!($($arg)+);
+        #[cfg(feature = "log")]
+        log::trace!($($arg)+);
+    }};
+}
+
+/// Instrumentation of a single conversion, see [`instrument!`].
+#[derive(Debug)]
+pub(crate) struct Instrument {
+    #[cfg(feature = "tracing")]
+    span: tracing::span::EnteredSpan,
+    #[cfg(feature = "log")]
+    name: &'static str,
+    #[cfg(feature = "log")]
+    input_len: usize,
+    #[cfg(feature = "tracing")]
+    line_comments: usize,
+    #[cfg(feature = "tracing")]
+    block_comments: usize,
+    #[cfg(feature = "tracing")]
+    trailing_commas: usize,
+}
+
+impl Instrument {
+    #[cfg(feature = "tracing")]
+    #[inline]
+    #[allow(unused_variables)]
+    pub(crate) fn new(name: &'static str, input_len: usize, span: tracing::Span) -> Self {
+        Self {
+            span: span.entered(),
+            #[cfg(feature = "log")]
+            name,
+            #[cfg(feature = "log")]
+            input_len,
+            line_comments: 0,
+            block_comments: 0,
+            trailing_commas: 0,
+        }
+    }
+
+    #[cfg(not(feature = "tracing"))]
+    #[inline(always)]
+    #[allow(unused_variables)]
+    pub(crate) fn new(name: &'static str, input_len: usize) -> Self {
+        Self {
+            #[cfg(feature = "log")]
+            name,
+            #[cfg(feature = "log")]
+            input_len,
+        }
+    }
+
+    /// Called for each removed part of the input.
+    #[inline(always)]
+    #[allow(unused_variables)]
+    pub(crate) fn removed(&mut self, removed: Removed, range: Range<usize>) {
+        match removed {
+            Removed::LineComment => {
+                #[cfg(feature = "tracing")]
+                {
+                    self.line_comments += 1;
+                }
+                trace!("removed line comment at byte {}", range.start);
+            }
+            Removed::BlockComment => {
+                #[cfg(feature = "tracing")]
+                {
+                    self.block_comments += 1;
+                }
+                trace!("removed block comment at byte {}", range.start);
+            }
+            Removed::TrailingComma { .. } => {
+                #[cfg(feature = "tracing")]
+                {
+                    self.trailing_commas += 1;
+                }
+                trace!("removed trailing comma at byte {}", range.start);
+            }
+        }
+    }
+
+    /// Called when the conversion is done.
+    #[inline(always)]
+    #[allow(unused_variables)]
+    pub(crate) fn finish(self, output_len: usize) {
+        #[cfg(feature = "tracing")]
+        {
+            self.span.record("output_len", output_len);
+            self.span
+                .record("line_comments_removed", self.line_comments);
+            self.span
+                .record("block_comments_removed", self.block_comments);
+            self.span
+                .record("trailing_commas_removed", self.trailing_commas);
+        }
+
+        #[cfg(feature = "log")]
+        log::debug!("{}: {} → {} bytes", self.name, self.input_len, output_len);
+    }
+}
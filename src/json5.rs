@@ -0,0 +1,396 @@
+use std::borrow::Cow;
+
+use crate::offsets::OffsetMap;
+
+/// [JSON5] features, which are turned into [JSON with Comments],
+/// see [`JsonCConfig::allow_single_quoted_strings()`] and
+/// [`JsonCConfig::allow_unquoted_unicode_keys()`].
+///
+/// [JSON5]: https://json5.org
+/// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+/// [`JsonCConfig::allow_single_quoted_strings()`]: crate::JsonCConfig::allow_single_quoted_strings
+/// [`JsonCConfig::allow_unquoted_unicode_keys()`]: crate::JsonCConfig::allow_unquoted_unicode_keys
+#[derive(PartialEq, Eq, Clone, Copy, Default, Debug)]
+pub(crate) struct Json5 {
+    pub(crate) single_quoted_strings: bool,
+    pub(crate) unquoted_keys: bool,
+}
+
+impl Json5 {
+    #[inline]
+    pub(crate) fn is_enabled(self) -> bool {
+        self.single_quoted_strings || self.unquoted_keys
+    }
+
+    /// Replaces all single-quoted strings in `jsonc` with double-quoted
+    /// strings, i.e. `'a "b" \'c\''` is turned into `"a \"b\" 'c'"`,
+    /// and quotes all unquoted keys, i.e. `{café: 1}` is turned into
+    /// `{"café": 1}`, depending on which features are enabled.
+    ///
+    /// Comments and double-quoted strings are skipped, i.e. quotes inside
+    /// them are left as is. Unterminated single-quoted strings are also
+    /// left as is. Escape sequences in single-quoted strings, which are
+    /// not valid in [JSON], are rewritten, see [`push_requoted()`].
+    ///
+    /// The returned [`OffsetMap`] maps byte offsets in the output
+    /// back into `jsonc`.
+    ///
+    /// [JSON]: https://www.json.org/json-en.html
+    pub(crate) fn to_jsonc(
+        self,
+        jsonc: &str,
+        nested_block_comments: bool,
+    ) -> (Cow<'_, str>, OffsetMap) {
+        let bytes = jsonc.as_bytes();
+        let mut offsets = OffsetMap::default();
+
+        let mut json = String::with_capacity(jsonc.len());
+        // Start of the input, which has not been pushed to `json` yet
+        let mut start = 0;
+        // Last byte, which is not whitespace or part of a comment
+        let mut prev = None;
+
+        let mut i = 0;
+        while i < bytes.len() {
+            let c = jsonc[i..].chars().next().unwrap();
+            match (bytes[i], bytes.get(i + 1)) {
+                (b'"', _) => {
+                    i = skip_quoted(bytes, i, b'"').unwrap_or(bytes.len());
+                    prev = Some(b'"');
+                }
+                (b'/', Some(b'/' | b'*')) => i = skip_trivia(bytes, i, nested_block_comments),
+                (b'\'', _) if self.single_quoted_strings => {
+                    let Some(end) = skip_quoted(bytes, i, b'\'') else {
+                        break;
+                    };
+
+                    json.push_str(&jsonc[start..i]);
+                    offsets.push(json.len(), i);
+                    push_requoted(&mut json, &jsonc[(i + 1)..(end - 1)]);
+                    offsets.push(json.len(), end);
+                    start = end;
+                    i = end;
+                    prev = Some(b'"');
+                }
+                _ if self.unquoted_keys && is_identifier_start(c) => {
+                    let end = jsonc[i..]
+                        .char_indices()
+                        .find(|&(_, c)| !is_identifier_part(c))
+                        .map_or(bytes.len(), |(n, _)| i + n);
+
+                    let is_key = matches!(prev, Some(b'{' | b','))
+                        && (bytes.get(skip_trivia(bytes, end, nested_block_comments))
+                            == Some(&b':'));
+                    if is_key {
+                        json.push_str(&jsonc[start..i]);
+                        offsets.push(json.len(), i);
+                        json.push('"');
+                        json.push_str(&jsonc[i..end]);
+                        json.push('"');
+                        offsets.push(json.len(), end);
+                        start = end;
+                    }
+
+                    i = end;
+                    prev = Some(bytes[end - 1]);
+                }
+                (b, _) if b.is_ascii_whitespace() => i += 1,
+                (b, _) => {
+                    i += c.len_utf8();
+                    prev = Some(b);
+                }
+            }
+        }
+
+        if start == 0 {
+            return (Cow::Borrowed(jsonc), offsets);
+        }
+        json.push_str(&jsonc[start..]);
+        (Cow::Owned(json), offsets)
+    }
+}
+
+/// Returns the end of the string starting with `quote` at `start`,
+/// or `None` if the string is unterminated.
+fn skip_quoted(bytes: &[u8], start: usize, quote: u8) -> Option<usize> {
+    let mut i = start + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b if b == quote => return Some(i + 1),
+            _ => i += 1,
+        }
+    }
+    None
+}
+
+/// Returns the end of the whitespace and comments starting at `start`.
+fn skip_trivia(bytes: &[u8], start: usize, nested_block_comments: bool) -> usize {
+    let mut i = start;
+    while i < bytes.len() {
+        match (bytes[i], bytes.get(i + 1)) {
+            // Same as the tokenizer, i.e. line comments end at `\n` or `\r`
+            (b'/', Some(b'/')) => {
+                i = bytes[i..]
+                    .iter()
+                    .position(|&b| matches!(b, b'\n' | b'\r'))
+                    .map_or(bytes.len(), |n| i + n);
+            }
+            (b'/', Some(b'*')) => i = skip_block_comment(bytes, i, nested_block_comments),
+            (b, _) if b.is_ascii_whitespace() => i += 1,
+            _ => break,
+        }
+    }
+    i
+}
+
+/// Returns the end of the block comment starting at `start`.
+fn skip_block_comment(bytes: &[u8], start: usize, nested: bool) -> usize {
+    let mut depth = 0usize;
+    let mut i = start;
+    while i + 1 < bytes.len() {
+        match (bytes[i], bytes[i + 1]) {
+            (b'/', b'*') if nested || (depth == 0) => {
+                depth += 1;
+                i += 2;
+            }
+            (b'*', b'/') => {
+                depth -= 1;
+                i += 2;
+                if depth == 0 {
+                    return i;
+                }
+            }
+            _ => i += 1,
+        }
+    }
+    bytes.len()
+}
+
+/// Returns `true` if `c` is an ECMAScript `IdentifierStart`,
+/// excluding `\u` escape sequences.
+fn is_identifier_start(c: char) -> bool {
+    match c {
+        '$' | '_' => true,
+        c if c.is_ascii() => c.is_ascii_alphabetic(),
+        c => unicode_ident::is_xid_start(c),
+    }
+}
+
+/// Returns `true` if `c` is an ECMAScript `IdentifierPart`,
+/// excluding `\u` escape sequences.
+fn is_identifier_part(c: char) -> bool {
+    match c {
+        '$' | '\u{200C}' | '\u{200D}' => true,
+        c if c.is_ascii() => c.is_ascii_alphanumeric() || (c == '_'),
+        c => unicode_ident::is_xid_continue(c),
+    }
+}
+
+/// Pushes the contents of a single-quoted string as a double-quoted string.
+///
+/// Escape sequences, which are valid in [JSON5] but not in [JSON], are
+/// rewritten, i.e. `\'` into `'`, `\x41` into `\u0041`, `\v` into `\u000b`,
+/// `\0` into `\u0000`, and other escaped characters, e.g. `\a`, into the
+/// character itself. Line continuations, i.e. `\` followed by a line
+/// terminator, are removed.
+///
+/// [JSON5]: https://json5.org
+/// [JSON]: https://www.json.org/json-en.html
+fn push_requoted(json: &mut String, contents: &str) {
+    json.push('"');
+
+    let mut chars = contents.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => match chars.next() {
+                Some(c @ ('"' | '\\' | '/' | 'b' | 'f' | 'n' | 'r' | 't' | 'u')) => {
+                    json.push('\\');
+                    json.push(c);
+                }
+                Some('\'') => json.push('\''),
+                Some('v') => json.push_str("\\u000b"),
+                Some('0') if !chars.as_str().starts_with(|c: char| c.is_ascii_digit()) => {
+                    json.push_str("\\u0000");
+                }
+                Some('x') => match chars.as_str().get(..2) {
+                    Some(hex) if hex.bytes().all(|b| b.is_ascii_hexdigit()) => {
+                        json.push_str("\\u00");
+                        json.push_str(hex);
+                        chars.nth(1);
+                    }
+                    // Invalid, i.e. kept as is
+                    _ => json.push_str("\\x"),
+                },
+                Some('\r') => {
+                    if chars.as_str().starts_with('\n') {
+                        chars.next();
+                    }
+                }
+                Some('\n' | '\u{2028}' | '\u{2029}') => {}
+                // Invalid, i.e. kept as is
+                Some(c @ '0'..='9') => {
+                    json.push('\\');
+                    json.push(c);
+                }
+                Some(c) => json.push(c),
+                None => json.push('\\'),
+            },
+            c => json.push(c),
+        }
+    }
+
+    json.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SINGLE_QUOTED: Json5 = Json5 {
+        single_quoted_strings: true,
+        unquoted_keys: false,
+    };
+    const UNQUOTED_KEYS: Json5 = Json5 {
+        single_quoted_strings: false,
+        unquoted_keys: true,
+    };
+
+    #[test]
+    fn test_requote_single_quoted_strings() {
+        let cases = [
+            ("", ""),
+            ("{'a': 'b'}", "{\"a\": \"b\"}"),
+            ("['a \"b\" \\'c\\'']", "[\"a \\\"b\\\" 'c'\"]"),
+            ("['\\n\\\\', '']", "[\"\\n\\\\\", \"\"]"),
+            ("['// a', '/* b */']", "[\"// a\", \"/* b */\"]"),
+            (
+                "[\"'a'\", // 'b'\n/* 'c' */ 'd']",
+                "[\"'a'\", // 'b'\n/* 'c' */ \"d\"]",
+            ),
+            ("['æøå', '日本語']", "[\"æøå\", \"日本語\"]"),
+            ("['a', 'unterminated]", "[\"a\", 'unterminated]"),
+            ("['\\x41\\x7e']", "[\"\\u0041\\u007e\"]"),
+            ("['\\x4', '\\xzz']", "[\"\\x4\", \"\\xzz\"]"),
+            ("['\\v']", "[\"\\u000b\"]"),
+            ("['\\0', '\\01']", "[\"\\u0000\", \"\\01\"]"),
+            ("['a\\\nb\\\r\nc\\\rd\\\u{2028}e']", "[\"abcde\"]"),
+            ("['\\a\\$\\ø']", "[\"a$ø\"]"),
+            ("['\\/\\b\\f\\r\\t\\u00e6']", "[\"\\/\\b\\f\\r\\t\\u00e6\"]"),
+        ];
+        for (jsonc, expected) in cases {
+            assert_eq!(SINGLE_QUOTED.to_jsonc(jsonc, false).0, expected);
+        }
+
+        // JSON5 escape sequences are turned into valid JSON
+        for jsonc in ["['\\x41\\v\\0']", "['a\\\r\nb\\\u{2029}\\a']"] {
+            let json = SINGLE_QUOTED.to_jsonc(jsonc, false).0;
+            assert!(crate::jsonc_to_json_strict(&json).is_ok(), "{:?}", json);
+        }
+
+        let jsonc = "[/* /* 'a' */ 'b' */ 'c']";
+        assert_eq!(
+            SINGLE_QUOTED.to_jsonc(jsonc, false).0,
+            "[/* /* 'a' */ \"b\" */ \"c\"]"
+        );
+        assert_eq!(
+            SINGLE_QUOTED.to_jsonc(jsonc, true).0,
+            "[/* /* 'a' */ 'b' */ \"c\"]"
+        );
+
+        let jsonc = "[\"a\", // 'b'\n]";
+        assert!(matches!(
+            SINGLE_QUOTED.to_jsonc(jsonc, false).0,
+            Cow::Borrowed(_)
+        ));
+    }
+
+    #[test]
+    fn test_unquoted_keys() {
+        let cases = [
+            ("{a: 1}", "{\"a\": 1}"),
+            ("{café: 1, $_ẞ9: 2}", "{\"café\": 1, \"$_ẞ9\": 2}"),
+            (
+                "{日本語: {ü\u{200D}: null}}",
+                "{\"日本語\": {\"ü\u{200D}\": null}}",
+            ),
+            (
+                "{null /* a */ : true,\n// b\nfalse: 1}",
+                "{\"null\" /* a */ : true,\n// b\n\"false\": 1}",
+            ),
+            ("{\"a\": b, c: 'd'}", "{\"a\": b, \"c\": 'd'}"),
+            ("{\"a b\": 1, /* c: 2 */}", "{\"a b\": 1, /* c: 2 */}"),
+            ("[a, b: 1, {c}]", "[a, \"b\": 1, {c}]"),
+            ("{1a: 2}", "{1a: 2}"),
+            ("{a: 1, // c\rb: 2}", "{\"a\": 1, // c\r\"b\": 2}"),
+            ("{a // c\r: 1}", "{\"a\" // c\r: 1}"),
+        ];
+        for (jsonc, expected) in cases {
+            assert_eq!(
+                UNQUOTED_KEYS.to_jsonc(jsonc, false).0,
+                expected,
+                "{:?}",
+                jsonc
+            );
+        }
+
+        let json5 = Json5 {
+            single_quoted_strings: true,
+            unquoted_keys: true,
+        };
+        assert_eq!(
+            json5.to_jsonc("{a: 'b', 'c': {d: 'e'}}", false).0,
+            "{\"a\": \"b\", \"c\": {\"d\": \"e\"}}"
+        );
+
+        // Converts the same as `jsonc_to_json()`, after quoting
+        for jsonc in ["{a: 1 // c\r}", "{a: 1, // c\rb: 'd' // e\r\n}"] {
+            let json = crate::jsonc_to_json(&json5.to_jsonc(jsonc, false).0).into_owned();
+            assert!(crate::jsonc_to_json_strict(&json).is_ok(), "{:?}", json);
+        }
+    }
+
+    #[test]
+    fn test_offset_map_single_quoted() {
+        let jsonc = "['\\'b', 1]";
+        let (json, offsets) = SINGLE_QUOTED.to_jsonc(jsonc, false);
+        assert_eq!(json, "[\"'b\", 1]");
+
+        // Start of each token in the output and the input
+        let tokens = [(0, 0), (1, 1), (5, 6), (7, 8), (8, 9)];
+        for (output, input) in tokens {
+            assert_eq!(offsets.to_input(output), input, "{:?}", &json[output..]);
+        }
+    }
+
+    #[test]
+    fn test_offset_map() {
+        let json5 = Json5 {
+            single_quoted_strings: true,
+            unquoted_keys: true,
+        };
+
+        let jsonc = "{a: '\\'b\"', ø: 1}";
+        let (json, offsets) = json5.to_jsonc(jsonc, false);
+        assert_eq!(json, "{\"a\": \"'b\\\"\", \"ø\": 1}");
+
+        // Start of each token in the output and the input
+        let tokens = [
+            (0, 0),
+            (1, 1),
+            (4, 2),
+            (6, 4),
+            (12, 10),
+            (14, 12),
+            (19, 15),
+            (22, 18),
+        ];
+        for (output, input) in tokens {
+            assert_eq!(offsets.to_input(output), input, "{:?}", &json[output..]);
+        }
+
+        // The escaped `"` inside the rewritten string
+        assert_eq!(offsets.to_input(10), 8);
+    }
+}
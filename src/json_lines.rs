@@ -0,0 +1,128 @@
+use any_lexer::JsonCToken;
+
+use crate::strict::validate;
+use crate::tokens::JsonCTokens;
+use crate::JsonCError;
+
+/// Converts a [JSON with Comments] array into [JSON Lines], i.e. each
+/// element of the top-level array is output on its own line, without
+/// the surrounding array.
+///
+/// Comments, trailing commas, and whitespace outside strings are removed,
+/// such that each element fits on a single line. Each line, including
+/// the last, is terminated by `\n`.
+///
+/// Returns [`JsonCError`] if `jsonc` is not valid [JSON with Comments],
+/// see [`jsonc_to_json_strict()`], or if the top-level value is not
+/// an array, in which case [`JsonCError::UnexpectedToken`] is returned.
+///
+/// # Example
+///
+/// ```rust
+/// use jsonc_to_json::jsonc_array_to_json_lines;
+///
+/// let jsonc = r#"[
+///     {"id": 1, "tags": ["a", "b",]}, // First
+///     /* Second */
+///     {"id": 2, "tags": [],},
+/// ]"#;
+///
+/// let json_lines = jsonc_array_to_json_lines(jsonc)?;
+/// assert_eq!(
+///     json_lines,
+///     "{\"id\":1,\"tags\":[\"a\",\"b\"]}\n{\"id\":2,\"tags\":[]}\n"
+/// );
+/// # Ok::<(), jsonc_to_json::JsonCError>(())
+/// ```
+///
+/// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+/// [JSON Lines]: https://jsonlines.org
+/// [`jsonc_to_json_strict()`]: crate::jsonc_to_json_strict
+pub fn jsonc_array_to_json_lines(jsonc: &str) -> Result<String, JsonCError> {
+    let tokens = JsonCTokens::new(jsonc);
+    validate(tokens.clone())?;
+
+    let mut tokens = tokens
+        .filter(|(tok, _span)| {
+            !matches!(
+                tok,
+                JsonCToken::Space | JsonCToken::LineComment | JsonCToken::BlockComment
+            )
+        })
+        .peekable();
+
+    match tokens.next() {
+        Some((_tok, span)) if span.as_str() == "[" => {}
+        Some((_tok, span)) => {
+            return Err(JsonCError::UnexpectedToken {
+                byte_offset: span.start(),
+            });
+        }
+        // Unreachable as `validate()` errors for empty input
+        None => return Ok(String::new()),
+    }
+
+    let mut json_lines = String::with_capacity(jsonc.len());
+    let mut line_len = 0;
+    let mut depth = 1_usize;
+
+    while let Some((_tok, span)) = tokens.next() {
+        let s = span.as_str();
+        match s {
+            "," if depth == 1 => {
+                if line_len > 0 {
+                    json_lines.push('\n');
+                    line_len = 0;
+                }
+                continue;
+            }
+            "," => {
+                // Skip trailing commas
+                if let Some((_tok, next)) = tokens.peek() {
+                    if matches!(next.as_str(), "}" | "]") {
+                        continue;
+                    }
+                }
+            }
+            "{" | "[" => depth += 1,
+            "}" | "]" => depth -= 1,
+            _ => {}
+        }
+
+        if depth == 0 {
+            break;
+        }
+
+        json_lines.push_str(s);
+        line_len += s.len();
+    }
+
+    if line_len > 0 {
+        json_lines.push('\n');
+    }
+
+    Ok(json_lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_lines() {
+        assert_eq!(jsonc_array_to_json_lines("[]"), Ok(String::new()));
+        assert!(jsonc_array_to_json_lines("[,]").is_err());
+        assert_eq!(
+            jsonc_array_to_json_lines("[1, \"a, b\", [2, [3,],], null,]"),
+            Ok("1\n\"a, b\"\n[2,[3]]\nnull\n".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_json_lines_not_array() {
+        assert_eq!(
+            jsonc_array_to_json_lines("// Comment\n{\"a\": 1}"),
+            Err(JsonCError::UnexpectedToken { byte_offset: 11 })
+        );
+    }
+}
@@ -0,0 +1,158 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::ops::Deref;
+use std::sync::OnceLock;
+
+use crate::{jsonc_to_json, JsonCToJsonIter};
+
+/// Returns a [`LazyJsonC`], which defers converting `jsonc`
+/// until it is actually used.
+///
+/// See [`LazyJsonC`] for more information.
+#[must_use]
+#[inline]
+pub fn lazy_jsonc(jsonc: &str) -> LazyJsonC<'_> {
+    LazyJsonC::new(jsonc)
+}
+
+/// Lazily converted [JSON with Comments], which defers the conversion
+/// until it is actually used.
+///
+/// - [`Deref`] converts using [`jsonc_to_json()`] on first access,
+///   and caches the result for subsequent accesses.
+/// - [`Display`] writes the converted [JSON] without allocating, using
+///   [`JsonCToJsonIter`], or the cached result if it has already been converted.
+///
+/// This is useful in hot paths where the converted [JSON] might not
+/// be used, e.g. when logging is gated by the log level.
+///
+/// `LazyJsonC` is [`Send`] and [`Sync`].
+///
+/// # Example
+///
+/// ```rust
+/// use jsonc_to_json::lazy_jsonc;
+///
+/// let jsonc = "{\"arr\": [1, 2,/* Comment */ 3, 4,,]}// Line Comment";
+///
+/// let json = lazy_jsonc(jsonc);
+///
+/// // Does not allocate
+/// assert_eq!(json.to_string(), "{\"arr\": [1, 2, 3, 4]}");
+///
+/// // Converts and caches the result
+/// assert_eq!(&*json, "{\"arr\": [1, 2, 3, 4]}");
+/// ```
+///
+/// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+/// [JSON]: https://www.json.org/json-en.html
+/// [`Display`]: fmt::Display
+#[derive(Clone, Debug)]
+pub struct LazyJsonC<'jsonc> {
+    jsonc: &'jsonc str,
+    json: OnceLock<Cow<'jsonc, str>>,
+}
+
+impl<'jsonc> LazyJsonC<'jsonc> {
+    /// See [`lazy_jsonc()`] for more information.
+    #[must_use]
+    #[inline]
+    pub fn new(jsonc: &'jsonc str) -> Self {
+        Self {
+            jsonc,
+            json: OnceLock::new(),
+        }
+    }
+
+    /// Returns the unconverted [JSON with Comments].
+    ///
+    /// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+    #[must_use]
+    #[inline]
+    pub fn jsonc(&self) -> &'jsonc str {
+        self.jsonc
+    }
+
+    /// Returns the converted [JSON], converting it if it
+    /// has not been converted yet.
+    ///
+    /// [JSON]: https://www.json.org/json-en.html
+    #[must_use]
+    #[inline]
+    pub fn json(&self) -> &str {
+        self.json.get_or_init(|| jsonc_to_json(self.jsonc))
+    }
+
+    /// Returns `true` if the [JSON with Comments] has been converted.
+    ///
+    /// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+    #[must_use]
+    #[inline]
+    pub fn is_converted(&self) -> bool {
+        self.json.get().is_some()
+    }
+
+    /// Returns the converted [JSON], converting it if it
+    /// has not been converted yet.
+    ///
+    /// [JSON]: https://www.json.org/json-en.html
+    #[must_use]
+    #[inline]
+    pub fn into_json(self) -> Cow<'jsonc, str> {
+        match self.json.into_inner() {
+            Some(json) => json,
+            None => jsonc_to_json(self.jsonc),
+        }
+    }
+}
+
+impl Deref for LazyJsonC<'_> {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.json()
+    }
+}
+
+impl AsRef<str> for LazyJsonC<'_> {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.json()
+    }
+}
+
+impl fmt::Display for LazyJsonC<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(json) = self.json.get() {
+            return f.write_str(json);
+        }
+
+        for part in JsonCToJsonIter::new(self.jsonc) {
+            f.write_str(part)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lazy_display_does_not_convert() {
+        let json = lazy_jsonc("[1, 2, /* Comment */ 3,]");
+        assert_eq!(json.to_string(), "[1, 2,  3]");
+        assert!(!json.is_converted());
+
+        assert_eq!(&*json, "[1, 2,  3]");
+        assert!(json.is_converted());
+        assert_eq!(json.to_string(), "[1, 2,  3]");
+    }
+
+    #[test]
+    fn test_lazy_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<LazyJsonC<'_>>();
+    }
+}
@@ -9,6 +9,10 @@
 //! parser. Instead it uses a [JSON with Comments] tokenizer, which makes
 //! conversion a lot faster.
 //!
+//! Only comments and trailing commas are removed. Everything else is kept
+//! as is, e.g. the `"$schema"` property used by editors such as VS Code is
+//! regular [JSON], and is kept by every conversion, including strict mode.
+//!
 //! Currently `#![no_std]` is not supported. It will however be added, when
 //! some upstream changes have been applied.
 //!
@@ -98,10 +102,144 @@
 #![warn(clippy::all)]
 
 use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::iter::FusedIterator;
 use std::ops::Range;
+use std::sync::Arc;
+
+use any_lexer::{JsonCToken as LexerToken, TokenSpan};
+
+mod adapters;
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+#[cfg(feature = "async-std")]
+mod async_std;
+#[cfg(feature = "bumpalo")]
+mod bump;
+mod bytes;
+mod comments;
+#[cfg(feature = "compact_str")]
+mod compact;
+mod config;
+#[cfg(feature = "flate2")]
+mod deflate;
+mod diagnostics;
+mod diff;
+mod doc;
+mod duplicate;
+mod error;
+mod file;
+mod flags;
+mod header;
+mod incremental;
+mod instrument;
+#[cfg(feature = "from_json5")]
+mod json5;
+mod json_lines;
+mod lazy;
+#[cfg(feature = "lint")]
+mod lint;
+#[cfg(feature = "merge")]
+mod merge;
+mod offsets;
+#[cfg(feature = "rayon")]
+mod par;
+mod preprocess;
+mod reader;
+#[cfg(feature = "serde_json")]
+mod ser;
+#[cfg(feature = "smol_str")]
+mod smol;
+#[cfg(feature = "indexmap")]
+mod sorted;
+mod span;
+mod stats;
+mod stream;
+mod strict;
+mod syntax;
+#[cfg(feature = "testing")]
+pub mod testing;
+mod token_iter;
+mod tokens;
+mod valid;
+#[cfg(feature = "serde_json")]
+mod value;
+mod vars;
+
+pub use self::adapters::{
+    jsonc_to_json_window_iter, InspectJsonCIter, OffsetJsonCIter, TakeWhileDepthZero,
+    WindowJsonCIter,
+};
+#[cfg(feature = "arbitrary")]
+pub use self::arbitrary::JsonCInput;
+#[cfg(feature = "async-std")]
+pub use self::async_std::jsonc_to_json_from_async_std_read;
+#[cfg(feature = "bumpalo")]
+pub use self::bump::jsonc_to_json_in;
+pub use self::bytes::{
+    jsonc_to_json_from_bytes, jsonc_to_json_from_bytes_lossy, jsonc_to_json_null_delimited,
+};
+pub use self::comments::{
+    diff_comments, jsonc_extract_comments, jsonc_to_json_replace_with, CommentDiff, CommentKind,
+    ExtractedComment,
+};
+#[cfg(feature = "compact_str")]
+pub use self::compact::jsonc_to_json_compact;
+pub use self::config::{jsonc_to_json_with_config, JsonCConfig, LineEnding};
+#[cfg(feature = "flate2")]
+pub use self::deflate::JsonCToJsonDeflateWriter;
+pub use self::diagnostics::{
+    jsonc_to_json_with_diagnostics, CommentSpan, ConversionWarning, JsonCDiagnostics,
+};
+pub use self::diff::{jsonc_to_json_diff, Edit, EditKind};
+pub use self::doc::JsonCDoc;
+pub use self::duplicate::DuplicateKeyPolicy;
+pub use self::error::JsonCError;
+pub use self::file::{jsonc_to_json_chunked, jsonc_to_json_file, jsonc_to_json_file_in_place};
+pub use self::flags::StripFlags;
+pub use self::header::{json_to_jsonc, json_to_jsonc_with_config};
+pub use self::incremental::IncrementalJsonC;
+pub use self::json_lines::jsonc_array_to_json_lines;
+pub use self::lazy::{lazy_jsonc, LazyJsonC};
+#[cfg(feature = "lint")]
+pub use self::lint::{
+    find_commented_out_json, jsonc_lint, jsonc_lint_with_config, CommentedOutJson, LintConfig,
+    LintKind, LintWarning,
+};
+#[cfg(feature = "merge")]
+pub use self::merge::jsonc_merge;
+#[cfg(feature = "rayon")]
+pub use self::par::jsonc_to_json_par_batch;
+pub use self::preprocess::{JsonCPreprocessor, Stage};
+pub use self::reader::JsonCToJsonReader;
+#[cfg(feature = "serde_json")]
+pub use self::ser::{to_jsonc_string, JsonCFormatter, JsonCSerializer};
+#[cfg(feature = "smol_str")]
+pub use self::smol::jsonc_to_json_smol;
+#[cfg(feature = "indexmap")]
+pub use self::sorted::jsonc_to_json_sorted;
+pub use self::span::Span;
+pub use self::stats::{jsonc_to_json_with_stats, ConversionStats};
+pub use self::stream::JsonCToJsonBufReader;
+pub use self::strict::{jsonc_to_json_strict, validate_jsonc, JsonCValidator};
+pub use self::syntax::{
+    count_jsonc_tokens, has_jsonc_syntax, is_valid_json_without_conversion,
+    jsonc_to_json_estimate_output_len, jsonc_to_json_savings, jsonc_to_json_savings_detail,
+    CharSavings, JsonCSyntaxCount,
+};
+pub use self::token_iter::{jsonc_span_iter, JsonCToken};
+pub use self::valid::ValidJson;
+#[cfg(feature = "serde_json")]
+pub use self::value::{jsonc_to_value, JsonCValueError};
+pub use self::vars::{
+    jsonc_expand_vars, jsonc_expand_vars_with, jsonc_to_json_from_path_with_env,
+    jsonc_to_json_from_path_with_env_or, UnknownVar,
+};
 
-use any_lexer::{JsonCLexer, JsonCToken, Lexer, TokenSpan};
+use self::instrument::{instrument, Instrument};
+use self::tokens::JsonCTokens;
 
 /// Removes all [JSON with Comments] parts from `jsonc`, turning it into
 /// valid [JSON], i.e. removing line comments, block comments, and trailing
@@ -115,6 +253,21 @@ use any_lexer::{JsonCLexer, JsonCToken, Lexer, TokenSpan};
 /// is returned, otherwise a new [`String`] is allocated and <code>[Cow]::[Owned]</code>
 /// is returned.
 ///
+/// More precisely, <code>[Cow]::[Borrowed]</code> is returned when the output
+/// is a contiguous prefix of `jsonc`, i.e. when nothing is removed, or when
+/// everything removed is located at the end of `jsonc`. If anything is removed
+/// before the end, e.g. the comment in `[1, /* Comment */ 2]`, then the output
+/// is no longer contiguous, and a new [`String`] is allocated, even if only a
+/// few bytes are removed. Run `cargo run --release --example cow_overhead`
+/// to compare such inputs, against inputs resulting in <code>[Cow]::[Borrowed]</code>.
+///
+/// Since inputs without comments never allocate, checking for `/` upfront,
+/// e.g. using [`memchr`], only saves the tokenization, not an allocation.
+///
+/// The output is a subsequence of `jsonc`, so the number of bytes removed
+/// is `jsonc.len() - json.len()`, e.g. for sizing downstream buffers. See
+/// [`jsonc_to_json_with_stats()`] for counting what was removed.
+///
 /// **Warning:** The conversion is infallible and does not validate `jsonc`.
 /// If it contains invalid [JSON] or invalid [JSON with Comments], then the
 /// invalid parts are included in the result, i.e. `{foo,/*comment*/bar,}`
@@ -147,25 +300,45 @@ use any_lexer::{JsonCLexer, JsonCToken, Lexer, TokenSpan};
 /// [JSON]: https://www.json.org/json-en.html
 /// [Borrowed]: Cow::Borrowed
 /// [Owned]: Cow::Owned
+/// [`memchr`]: https://docs.rs/memchr
 /// [`serde_json` example]: crate#serde-example
+#[must_use = "this returns the converted JSON; dropping it does nothing"]
 pub fn jsonc_to_json(jsonc: &str) -> Cow<'_, str> {
-    let mut iter = JsonCToJsonIter::new(jsonc);
+    let instrument = instrument!("jsonc_to_json", jsonc);
+    collect_cow(JsonCToJsonIter::new(jsonc), instrument)
+}
+
+#[inline]
+fn collect_cow(mut iter: JsonCToJsonIter<'_>, mut instrument: Instrument) -> Cow<'_, str> {
+    let mut on_removed = |removed, range| instrument.removed(removed, range);
+    let capacity = iter.tokens.text().len();
+    let json = collect_cow_with(capacity, || iter.next_with(&mut on_removed));
+    instrument.finish(json.len());
+    json
+}
 
-    let first = match iter.next() {
+/// If more than a single part is yielded, then the output is
+/// allocated with `capacity`, i.e. usually the length of the input,
+/// as the output is never longer than the input.
+fn collect_cow_with<'jsonc, F>(capacity: usize, mut next: F) -> Cow<'jsonc, str>
+where
+    F: FnMut() -> Option<&'jsonc str>,
+{
+    let first = match next() {
         Some(first) => first,
         None => return Cow::Borrowed(""),
     };
 
-    let second = match iter.next() {
+    let second = match next() {
         Some(second) => second,
         None => return Cow::Borrowed(first),
     };
 
-    let mut json = String::new();
+    let mut json = String::with_capacity(capacity);
     json.push_str(first);
     json.push_str(second);
 
-    for part in iter {
+    while let Some(part) = next() {
         json.push_str(part);
     }
 
@@ -205,9 +378,230 @@ pub fn jsonc_to_json(jsonc: &str) -> Cow<'_, str> {
 /// [`serde_json` example]: crate#serde-example
 #[inline]
 pub fn jsonc_to_json_into(jsonc: &str, json: &mut String) {
+    let mut instrument = instrument!("jsonc_to_json_into", jsonc);
+    let mut on_removed = |removed, range| instrument.removed(removed, range);
+
+    let len = json.len();
+    let mut iter = JsonCToJsonIter::new(jsonc);
+    while let Some(part) = iter.next_with(&mut on_removed) {
+        json.push_str(part);
+    }
+
+    instrument.finish(json.len() - len);
+}
+
+/// Same as [`jsonc_to_json_into()`], but appends the output [JSON]
+/// as bytes to `json`.
+///
+/// **Note:** The output [JSON] is appended to `json`, i.e. if `json`
+/// is not empty, then call [`clear()`] beforehand.
+///
+/// See [`jsonc_to_json()`] for more information.
+///
+/// # Example
+///
+/// ```rust
+/// # use jsonc_to_json::jsonc_to_json_into_vec;
+/// let jsonc = "{\"arr\": [1, 2,/* Comment */ 3, 4,,]}// Line Comment";
+///
+/// let mut json = Vec::new();
+/// jsonc_to_json_into_vec(jsonc, &mut json);
+/// assert_eq!(json, b"{\"arr\": [1, 2, 3, 4]}");
+/// ```
+///
+/// [JSON]: https://www.json.org/json-en.html
+/// [`clear()`]: Vec::clear
+#[inline]
+pub fn jsonc_to_json_into_vec(jsonc: &str, json: &mut Vec<u8>) {
     for part in JsonCToJsonIter::new(jsonc) {
+        json.extend_from_slice(part.as_bytes());
+    }
+}
+
+/// Same as [`jsonc_to_json_into()`], but converts each of the `inputs`,
+/// and appends all the output [JSON] to `json`.
+///
+/// **Note:** Nothing is inserted between the outputs, i.e. push a
+/// separator such as `\n` to the end of each input if needed.
+///
+/// # Example
+///
+/// ```rust
+/// # use jsonc_to_json::jsonc_to_json_extend;
+/// let inputs = ["[1, 2,] // Comment\n", "{\"a\": /* Comment */ 3,}\n"];
+///
+/// let mut json = String::new();
+/// jsonc_to_json_extend(inputs, &mut json);
+/// assert_eq!(json, "[1, 2] \n{\"a\":  3}\n");
+/// ```
+///
+/// [JSON]: https://www.json.org/json-en.html
+pub fn jsonc_to_json_extend<'jsonc, I>(inputs: I, json: &mut String)
+where
+    I: IntoIterator<Item = &'jsonc str>,
+{
+    for jsonc in inputs {
+        jsonc_to_json_into(jsonc, json);
+    }
+}
+
+/// Same as [`jsonc_to_json()`], but converts `json` in place, and
+/// returns `true` if anything was removed, i.e. if `json` was modified.
+///
+/// The kept parts are moved to the front of `json`, i.e. the existing
+/// allocation is reused and its capacity is left unchanged.
+///
+/// See [`jsonc_to_json()`] for more information.
+///
+/// # Example
+///
+/// ```rust
+/// # use jsonc_to_json::jsonc_to_json_in_place;
+/// let mut json = String::from("{\"arr\": [1, 2,/* Comment */ 3, 4,,]}// Line Comment");
+/// assert!(jsonc_to_json_in_place(&mut json));
+/// assert_eq!(json, "{\"arr\": [1, 2, 3, 4]}");
+///
+/// // Already valid JSON is left as is
+/// assert!(!jsonc_to_json_in_place(&mut json));
+/// assert_eq!(json, "{\"arr\": [1, 2, 3, 4]}");
+/// ```
+pub fn jsonc_to_json_in_place(json: &mut String) -> bool {
+    let (kept, removed) = JsonCToJsonIter::new(json).into_parts();
+    if removed.is_empty() {
+        return false;
+    }
+
+    let mut kept = kept.into_iter().peekable();
+    let mut offset = 0;
+    json.retain(|ch| {
+        while kept.next_if(|range| range.end <= offset).is_some() {}
+        let keep = kept.peek().is_some_and(|range| range.contains(&offset));
+        offset += ch.len_utf8();
+        keep
+    });
+
+    true
+}
+
+/// Same as [`jsonc_to_json()`], but always returns an owned [`String`],
+/// which is allocated with the given `capacity` up front.
+///
+/// The output is never longer than the input, so a `capacity` of
+/// `jsonc.len()` avoids reallocating while converting.
+///
+/// See [`jsonc_to_json()`] for more information.
+///
+/// # Example
+///
+/// ```rust
+/// # use jsonc_to_json::jsonc_to_json_with_capacity;
+/// let jsonc = "{\"arr\": [1, 2,/* Comment */ 3, 4,,]}// Line Comment";
+///
+/// let json = jsonc_to_json_with_capacity(jsonc, jsonc.len());
+/// assert_eq!(json, "{\"arr\": [1, 2, 3, 4]}");
+/// assert!(json.capacity() >= jsonc.len());
+/// ```
+///
+/// [JSON]: https://www.json.org/json-en.html
+#[must_use = "this returns the converted JSON; dropping it does nothing"]
+pub fn jsonc_to_json_with_capacity(jsonc: &str, capacity: usize) -> String {
+    let mut instrument = instrument!("jsonc_to_json_with_capacity", jsonc);
+    let mut on_removed = |removed, range| instrument.removed(removed, range);
+
+    let mut json = String::with_capacity(capacity);
+    let mut iter = JsonCToJsonIter::new(jsonc);
+    while let Some(part) = iter.next_with(&mut on_removed) {
         json.push_str(part);
     }
+
+    instrument.finish(json.len());
+    json
+}
+
+/// Same as [`jsonc_to_json()`], but returns the [JSON] as an <code>[Arc]<[str]></code>.
+///
+/// If `jsonc` is already valid [JSON], then it is copied directly into
+/// the [`Arc`], i.e. without first allocating an intermediate [`String`].
+///
+/// # Example
+///
+/// ```rust
+/// # use jsonc_to_json::jsonc_to_json_arc;
+/// let jsonc = "{\"arr\": [1, 2,/* Comment */ 3, 4,,]}// Line Comment";
+///
+/// let json = jsonc_to_json_arc(jsonc);
+/// assert_eq!(&*json, "{\"arr\": [1, 2, 3, 4]}");
+/// ```
+///
+/// [JSON]: https://www.json.org/json-en.html
+#[must_use = "this returns the converted JSON; dropping it does nothing"]
+#[inline]
+pub fn jsonc_to_json_arc(jsonc: &str) -> Arc<str> {
+    match jsonc_to_json(jsonc) {
+        Cow::Borrowed(json) => Arc::from(json),
+        Cow::Owned(json) => Arc::from(json),
+    }
+}
+
+/// Same as [`jsonc_to_json()`], but returns the [JSON] as a <code>[Box]<[str]></code>.
+///
+/// If `jsonc` is already valid [JSON], then it is copied directly into
+/// the [`Box`], otherwise the allocated [`String`] is reused.
+///
+/// # Example
+///
+/// ```rust
+/// # use jsonc_to_json::jsonc_to_json_boxed;
+/// let jsonc = "{\"arr\": [1, 2,/* Comment */ 3, 4,,]}// Line Comment";
+///
+/// let json = jsonc_to_json_boxed(jsonc);
+/// assert_eq!(&*json, "{\"arr\": [1, 2, 3, 4]}");
+/// ```
+///
+/// [JSON]: https://www.json.org/json-en.html
+#[must_use = "this returns the converted JSON; dropping it does nothing"]
+#[inline]
+pub fn jsonc_to_json_boxed(jsonc: &str) -> Box<str> {
+    match jsonc_to_json(jsonc) {
+        Cow::Borrowed(json) => Box::from(json),
+        Cow::Owned(json) => json.into_boxed_str(),
+    }
+}
+
+/// Same as [`jsonc_to_json()`], but only strips the [JSON with Comments]
+/// features specified by `flags`.
+///
+/// This is useful when the consuming [JSON] parser accepts some of the
+/// features, e.g. trailing commas but not comments. Using
+/// [`StripFlags::ALL`] produces the same output as [`jsonc_to_json()`].
+///
+/// # Example
+///
+/// ```rust
+/// use jsonc_to_json::{jsonc_to_json_selective, StripFlags};
+///
+/// let jsonc = "[1, 2, /* Comment */ 3,] // Line Comment";
+///
+/// let json = jsonc_to_json_selective(jsonc, StripFlags::STRIP_COMMENTS);
+/// assert_eq!(json, "[1, 2,  3,] ");
+///
+/// let json = jsonc_to_json_selective(jsonc, StripFlags::STRIP_TRAILING_COMMAS);
+/// assert_eq!(json, "[1, 2, /* Comment */ 3] // Line Comment");
+///
+/// let json = jsonc_to_json_selective(
+///     jsonc,
+///     StripFlags::STRIP_LINE_COMMENTS | StripFlags::STRIP_TRAILING_COMMAS,
+/// );
+/// assert_eq!(json, "[1, 2, /* Comment */ 3] ");
+/// ```
+///
+/// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+/// [JSON]: https://www.json.org/json-en.html
+#[must_use = "this returns the converted JSON; dropping it does nothing"]
+#[inline]
+pub fn jsonc_to_json_selective(jsonc: &str, flags: StripFlags) -> Cow<'_, str> {
+    let instrument = instrument!("jsonc_to_json_selective", jsonc);
+    collect_cow(JsonCToJsonIter::with_flags(jsonc, flags), instrument)
 }
 
 /// Non-allocating and zero-copy [`Iterator`] that yields string slices
@@ -218,6 +612,12 @@ pub fn jsonc_to_json_into(jsonc: &str, json: &mut String) {
 /// invalid parts are included in the result, i.e. `{foo,/*comment*/bar,}`
 /// is turned into `{foo,bar}`.
 ///
+/// **Note:** Whether something is a comment or a trailing comma can only
+/// be determined by tokenizing everything before it. So skipping string
+/// slices using e.g. [`nth()`] is not O(1), as it must still tokenize all
+/// the skipped input. Likewise [`count()`] and [`last()`] tokenize all
+/// remaining input.
+///
 /// See [`jsonc_to_json()`] for more information.
 ///
 /// # Example
@@ -234,40 +634,163 @@ pub fn jsonc_to_json_into(jsonc: &str, json: &mut String) {
 /// ```
 ///
 /// [JSON]: https://www.json.org/json-en.html
+/// [`nth()`]: Iterator::nth
+/// [`count()`]: Iterator::count
+/// [`last()`]: Iterator::last
 #[inline]
 pub fn jsonc_to_json_iter(jsonc: &str) -> JsonCToJsonIter<'_> {
     JsonCToJsonIter::new(jsonc)
 }
 
+/// Non-allocating and zero-copy [`Iterator`] that yields the bytes
+/// of valid [JSON], one byte at a time.
+///
+/// This is the same as [`jsonc_to_json_iter()`], but flattened into bytes,
+/// e.g. for writing the output one byte at a time, without buffering it.
+///
+/// See [`jsonc_to_json()`] for more information.
+///
+/// # Example
+///
+/// ```rust
+/// # use jsonc_to_json::jsonc_to_json_bytes_iter;
+/// let jsonc = "[1, /* Comment */ 2,]";
+///
+/// let bytes = jsonc_to_json_bytes_iter(jsonc).collect::<Vec<u8>>();
+/// assert_eq!(bytes, b"[1,  2]");
+/// ```
+///
+/// [JSON]: https://www.json.org/json-en.html
+#[inline]
+pub fn jsonc_to_json_bytes_iter(jsonc: &str) -> impl FusedIterator<Item = u8> + Clone + '_ {
+    JsonCToJsonIter::new(jsonc).flat_map(str::bytes)
+}
+
 /// See [`jsonc_to_json_iter()`] for more information.
+#[must_use = "iterators are lazy and do nothing unless consumed"]
 #[derive(Clone, Debug)]
 pub struct JsonCToJsonIter<'jsonc> {
-    lexer: JsonCLexer<'jsonc>,
+    tokens: JsonCTokens<'jsonc>,
     next: Option<Range<usize>>,
+    /// Remaining parts, buffered by the first call to
+    /// [`next_back()`](DoubleEndedIterator::next_back).
+    back: VecDeque<Range<usize>>,
+    flags: StripFlags,
+    /// Byte offset of the end of the last yielded string slice,
+    /// see [`current_position()`](Self::current_position).
+    end: usize,
 }
 
 impl<'jsonc> JsonCToJsonIter<'jsonc> {
     /// See [`jsonc_to_json_iter()`] for more information.
+    #[inline]
     pub fn new(jsonc: &'jsonc str) -> Self {
+        Self::with_flags(jsonc, StripFlags::ALL)
+    }
+
+    /// Same as [`new()`](Self::new), but only strips the features
+    /// specified by `flags`.
+    ///
+    /// See [`jsonc_to_json_selective()`] for more information.
+    #[inline]
+    pub fn with_flags(jsonc: &'jsonc str, flags: StripFlags) -> Self {
+        Self {
+            tokens: JsonCTokens::new(jsonc),
+            next: None,
+            back: VecDeque::new(),
+            flags,
+            end: 0,
+        }
+    }
+
+    /// Same as [`new()`](Self::new), but using the options specified
+    /// by `config`.
+    ///
+    /// See [`JsonCConfig`] for more information.
+    #[inline]
+    pub fn with_config(jsonc: &'jsonc str, config: &JsonCConfig) -> Self {
         Self {
-            lexer: JsonCLexer::new(jsonc),
+            tokens: JsonCTokens::new(jsonc).nested_block_comments(config.nested_block_comments),
             next: None,
+            back: VecDeque::new(),
+            flags: config.flags,
+            end: 0,
         }
     }
 }
 
-impl<'jsonc> Iterator for JsonCToJsonIter<'jsonc> {
-    type Item = &'jsonc str;
+impl JsonCToJsonIter<'static> {
+    /// Returns an iterator over an empty string,
+    /// i.e. which is immediately exhausted.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use jsonc_to_json::JsonCToJsonIter;
+    /// let mut iter = JsonCToJsonIter::empty();
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    #[inline]
+    pub fn empty() -> Self {
+        Self::new("")
+    }
+}
 
+impl Default for JsonCToJsonIter<'_> {
+    /// Same as [`JsonCToJsonIter::empty()`].
+    ///
+    /// As the empty string is `'static`, the default iterator
+    /// is available for any lifetime, e.g. for use with
+    /// [`Option::unwrap_or_default()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use jsonc_to_json::{jsonc_to_json_iter, JsonCToJsonIter};
+    /// fn iter(jsonc: Option<&str>) -> JsonCToJsonIter<'_> {
+    ///     jsonc.map(jsonc_to_json_iter).unwrap_or_default()
+    /// }
+    ///
+    /// assert_eq!(iter(Some("[1, 2,]")).collect::<String>(), "[1, 2]");
+    /// assert_eq!(iter(None).collect::<String>(), "");
+    /// ```
     #[inline]
-    fn next(&mut self) -> Option<Self::Item> {
+    fn default() -> Self {
+        JsonCToJsonIter::empty()
+    }
+}
+
+impl<'jsonc> JsonCToJsonIter<'jsonc> {
+    /// Same as [`next()`](Iterator::next), but calls `on_removed` for
+    /// each removed part of the input.
+    ///
+    /// **Note:** Due to lookahead, `on_removed` may be called for removed
+    /// parts, which are located after the returned string slice.
+    #[inline]
+    fn next_with<F>(&mut self, on_removed: &mut F) -> Option<&'jsonc str>
+    where
+        F: FnMut(Removed, Range<usize>),
+    {
+        let span = match self.back.pop_front() {
+            Some(span) => span,
+            None => self.next_range_with(on_removed)?,
+        };
+        self.end = span.end;
+        Some(&self.tokens.text()[span])
+    }
+
+    #[inline]
+    fn next_range_with<F>(&mut self, on_removed: &mut F) -> Option<Range<usize>>
+    where
+        F: FnMut(Removed, Range<usize>),
+    {
         let mut span = match self.next.take() {
             Some(span) => span,
-            None => self.lexer.next_valid_json_token()?,
+            None => self.tokens.next_valid_json_token(self.flags, on_removed)?,
         };
 
         loop {
-            let next = self.lexer.next_valid_json_token();
+            let next = self.tokens.next_valid_json_token(self.flags, on_removed);
             if let Some(next) = next {
                 match span.continue_range(&next) {
                     Some(new_span) => {
@@ -283,72 +806,607 @@ impl<'jsonc> Iterator for JsonCToJsonIter<'jsonc> {
             }
         }
 
-        Some(&self.lexer.scanner().text()[span])
+        Some(span)
+    }
+}
+
+impl<'jsonc> JsonCToJsonIter<'jsonc> {
+    /// Returns the state, which determines the remaining output, i.e.
+    /// the remaining input and the options, excluding any state which
+    /// is derived from the remaining input.
+    fn state(
+        &self,
+    ) -> (
+        &'jsonc str,
+        Option<&'jsonc str>,
+        impl Iterator<Item = &'jsonc str> + '_,
+    ) {
+        let text = self.tokens.text();
+        let next = self.next.clone().map(|next| &text[next]);
+        let back = self.back.iter().map(move |span| &text[span.clone()]);
+        (self.tokens.remaining_text(), next, back)
+    }
+
+    #[inline]
+    fn options(&self) -> (StripFlags, bool) {
+        (self.flags, self.tokens.is_nested_block_comments())
+    }
+}
+
+impl PartialEq for JsonCToJsonIter<'_> {
+    /// Returns `true` if both iterators yield the same
+    /// remaining output, by comparing their remaining
+    /// input and options.
+    fn eq(&self, other: &Self) -> bool {
+        let (remaining, next, back) = self.state();
+        let (other_remaining, other_next, other_back) = other.state();
+        (self.options() == other.options())
+            && (remaining == other_remaining)
+            && (next == other_next)
+            && back.eq(other_back)
+    }
+}
+
+impl Eq for JsonCToJsonIter<'_> {}
+
+impl Hash for JsonCToJsonIter<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let (remaining, next, back) = self.state();
+        self.options().hash(state);
+        remaining.hash(state);
+        next.hash(state);
+        for part in back {
+            part.hash(state);
+        }
+    }
+}
+
+impl<'jsonc> Iterator for JsonCToJsonIter<'jsonc> {
+    type Item = &'jsonc str;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_with(&mut |_, _| {})
+    }
+
+    /// Folds the remaining string slices, with the lookahead of
+    /// [`next()`](Iterator::next) integrated into the loop, which is
+    /// used by e.g. `collect::<String>()`.
+    fn fold<B, F>(mut self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        let text = self.tokens.text();
+        let mut acc = init;
+
+        // `next_back()` buffers all remaining ranges
+        if !self.back.is_empty() {
+            for span in self.back {
+                acc = f(acc, &text[span]);
+            }
+            return acc;
+        }
+
+        let mut on_removed = |_, _| {};
+        let mut span = match self.next.take() {
+            Some(span) => span,
+            None => match self
+                .tokens
+                .next_valid_json_token(self.flags, &mut on_removed)
+            {
+                Some(span) => span,
+                None => return acc,
+            },
+        };
+
+        while let Some(next) = self
+            .tokens
+            .next_valid_json_token(self.flags, &mut on_removed)
+        {
+            if span.end == next.start {
+                span.end = next.end;
+            } else {
+                acc = f(acc, &text[span]);
+                span = next;
+            }
+        }
+
+        f(acc, &text[span])
+    }
+
+    /// Returns the last string slice using [`fold()`](Iterator::fold),
+    /// i.e. this still tokenizes all remaining input, but avoids the
+    /// overhead of [`next()`](Iterator::next) for each string slice.
+    #[inline]
+    fn last(self) -> Option<Self::Item> {
+        // `next_back()` buffers all remaining ranges
+        if let Some(span) = self.back.back() {
+            return Some(&self.tokens.text()[span.clone()]);
+        }
+
+        self.fold(None, |_, part| Some(part))
+    }
+
+    /// Counts the remaining string slices, without producing them,
+    /// by only comparing the ranges of the remaining tokens.
+    fn count(mut self) -> usize {
+        // `next_back()` buffers all remaining ranges
+        if !self.back.is_empty() {
+            return self.back.len();
+        }
+
+        let mut on_removed = |_, _| {};
+        let mut prev = match self.next.take() {
+            Some(span) => span,
+            None => match self
+                .tokens
+                .next_valid_json_token(self.flags, &mut on_removed)
+            {
+                Some(span) => span,
+                None => return 0,
+            },
+        };
+
+        let mut count = 1;
+        while let Some(span) = self
+            .tokens
+            .next_valid_json_token(self.flags, &mut on_removed)
+        {
+            if prev.end != span.start {
+                count += 1;
+            }
+            prev = span;
+        }
+        count
+    }
+}
+
+impl<'jsonc> DoubleEndedIterator for JsonCToJsonIter<'jsonc> {
+    /// Returns the last remaining string slice.
+    ///
+    /// [JSON with Comments] cannot be tokenized in reverse, as whether
+    /// e.g. `//` starts a comment depends on what precedes it. Instead
+    /// the first call to `next_back()` converts and buffers the ranges
+    /// of all remaining string slices, i.e. this allocates unlike
+    /// [`next()`](Iterator::next).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use jsonc_to_json::jsonc_to_json_iter;
+    /// let jsonc = r#"{foo}/**/[1,2,3,,]"bar""#;
+    ///
+    /// let mut iter = jsonc_to_json_iter(jsonc);
+    /// assert_eq!(iter.next_back(), Some("]\"bar\""));
+    /// assert_eq!(iter.next(), Some("{foo}"));
+    /// assert_eq!(iter.next_back(), Some("[1,2,3"));
+    /// assert_eq!(iter.next(), None);
+    /// assert_eq!(iter.next_back(), None);
+    /// ```
+    ///
+    /// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+    fn next_back(&mut self) -> Option<Self::Item> {
+        while let Some(span) = self.next_range_with(&mut |_, _| {}) {
+            self.back.push_back(span);
+        }
+
+        let span = self.back.pop_back()?;
+        Some(&self.tokens.text()[span])
     }
 }
 
 impl FusedIterator for JsonCToJsonIter<'_> {}
 
+impl<'jsonc> IntoIterator for &JsonCToJsonIter<'jsonc> {
+    type Item = &'jsonc str;
+    type IntoIter = JsonCToJsonIter<'jsonc>;
+
+    /// Returns a clone of the iterator, i.e. iterating over `&iter` yields
+    /// the remaining string slices, without advancing `iter` itself.
+    /// The original iterator is unaffected, and can be advanced
+    /// independently afterwards.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use jsonc_to_json::jsonc_to_json_iter;
+    /// let jsonc = "[1, /* Comment */ 2,] // Comment";
+    ///
+    /// let mut iter = jsonc_to_json_iter(jsonc);
+    /// assert_eq!(iter.next(), Some("[1, "));
+    ///
+    /// let mut json = String::new();
+    /// for part in &iter {
+    ///     json.push_str(part);
+    /// }
+    /// assert_eq!(json, " 2] ");
+    ///
+    /// assert_eq!(iter.next(), Some(" 2"));
+    /// ```
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.clone()
+    }
+}
+
+impl<'jsonc> JsonCToJsonIter<'jsonc> {
+    /// Appends all remaining string slices to `json`.
+    ///
+    /// This reserves enough capacity in `json` upfront, such that
+    /// no reallocations occur while appending.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use jsonc_to_json::jsonc_to_json_iter;
+    /// let jsonc = r#"{foo}/**/[1,2,3,,]"bar""#;
+    ///
+    /// let mut json = String::from("// Converted\n");
+    /// jsonc_to_json_iter(jsonc).collect_into(&mut json);
+    /// assert_eq!(json, "// Converted\n{foo}[1,2,3]\"bar\"");
+    /// ```
+    pub fn collect_into(self, json: &mut String) {
+        json.reserve(self.remaining_len());
+        for part in self {
+            json.push_str(part);
+        }
+    }
+
+    /// Returns the 1-based `(line, column)` in the input of the end of
+    /// the last string slice yielded by [`next()`](Iterator::next), i.e.
+    /// the position after its last character. Returns `(1, 1)` if nothing
+    /// has been yielded yet.
+    ///
+    /// Lines are separated by `\n`, and columns are counted in [`char`]s.
+    ///
+    /// **Note:** The position is computed on demand, by scanning the input
+    /// up until the end of the last yielded string slice.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use jsonc_to_json::jsonc_to_json_iter;
+    /// let jsonc = "[\n  1, // Comment\n  2,\n]";
+    ///
+    /// let mut iter = jsonc_to_json_iter(jsonc);
+    /// assert_eq!(iter.current_position(), (1, 1));
+    /// assert_eq!(iter.next(), Some("[\n  1, "));
+    /// assert_eq!(iter.current_position(), (2, 6));
+    /// assert_eq!(iter.next(), Some("\n  2"));
+    /// assert_eq!(iter.current_position(), (3, 4));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn current_position(&self) -> (usize, usize) {
+        let before = &self.tokens.text()[..self.end];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let line = before[..line_start].matches('\n').count() + 1;
+        let column = before[line_start..].chars().count() + 1;
+        (line, column)
+    }
+
+    /// Drives the iterator to completion, and returns all remaining
+    /// string slices.
+    ///
+    /// Unlike collecting into a [`String`], the boundaries between the
+    /// slices are preserved, i.e. each slice is a contiguous part of the
+    /// input. Use [`concat()`](slice::concat) to get the output [JSON].
+    ///
+    /// See also [`into_parts()`](Self::into_parts) for the byte ranges
+    /// of the slices.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use jsonc_to_json::jsonc_to_json_iter;
+    /// let jsonc = r#"{foo}/**/[1,2,3,,]"bar""#;
+    ///
+    /// let parts = jsonc_to_json_iter(jsonc).collect_parts();
+    /// assert_eq!(parts, ["{foo}", "[1,2,3", "]\"bar\""]);
+    /// assert_eq!(parts.concat(), "{foo}[1,2,3]\"bar\"");
+    /// ```
+    ///
+    /// [JSON]: https://www.json.org/json-en.html
+    #[must_use]
+    #[inline]
+    pub fn collect_parts(self) -> Vec<&'jsonc str> {
+        self.collect()
+    }
+
+    /// Drives the iterator to completion, and returns the byte ranges of
+    /// the input which are kept in the output, and the byte ranges of the
+    /// comments and trailing commas which are removed, i.e.
+    /// `(kept_ranges, removed_ranges)`.
+    ///
+    /// Each kept range corresponds to a string slice yielded by the
+    /// iterator. Each removed range is a single comment or trailing comma.
+    ///
+    /// When called on a new iterator, the ranges together partition the
+    /// entire input. Otherwise, they only cover the remaining input.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use jsonc_to_json::jsonc_to_json_iter;
+    /// let jsonc = r#"{foo}/**/[1,2,3,,]"bar""#;
+    ///
+    /// let (kept, removed) = jsonc_to_json_iter(jsonc).into_parts();
+    /// assert_eq!(kept, [0..5, 9..15, 17..23]);
+    /// assert_eq!(removed, [5..9, 15..16, 16..17]);
+    /// ```
+    #[must_use]
+    pub fn into_parts(mut self) -> (Vec<Range<usize>>, Vec<Range<usize>>) {
+        let mut kept = Vec::new();
+        let mut removed = Vec::new();
+
+        // The removed ranges between the parts buffered by `next_back()`
+        // are not retained, so they are recovered as the gaps between them
+        if !self.back.is_empty() {
+            let end = self.tokens.text().len();
+            let mut iter = self.back.into_iter().peekable();
+            while let Some(span) = iter.next() {
+                let next_start = iter.peek().map_or(end, |next| next.start);
+                if span.end < next_start {
+                    removed.push(span.end..next_start);
+                }
+                kept.push(span);
+            }
+            return (kept, removed);
+        }
+
+        let mut on_removed = |_, range| removed.push(range);
+        while let Some(span) = self.next_range_with(&mut on_removed) {
+            kept.push(span);
+        }
+
+        (kept, removed)
+    }
+
+    /// Returns the part of the input, which has not been yielded yet.
+    ///
+    /// This allows using the iterator to convert only a prefix
+    /// of the input, and then process the rest separately.
+    ///
+    /// Comments and trailing commas, which have already been removed
+    /// due to lookahead, are not included in the returned string slice.
+    /// If [`next_back()`](DoubleEndedIterator::next_back) has been called,
+    /// then the parts yielded by it are also not included.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use jsonc_to_json::jsonc_to_json_iter;
+    /// let jsonc = "[1, 2,] /* Comment */ {\"foo\": \"bar\"}";
+    ///
+    /// let mut iter = jsonc_to_json_iter(jsonc);
+    /// assert_eq!(iter.as_remaining_str(), jsonc);
+    /// assert_eq!(iter.next(), Some("[1, 2"));
+    /// assert_eq!(iter.as_remaining_str(), "] /* Comment */ {\"foo\": \"bar\"}");
+    /// assert_eq!(iter.next(), Some("] "));
+    /// assert_eq!(iter.as_remaining_str(), " {\"foo\": \"bar\"}");
+    /// ```
+    #[must_use]
+    pub fn as_remaining_str(&self) -> &'jsonc str {
+        let text = self.tokens.text();
+        if let (Some(first), Some(last)) = (self.back.front(), self.back.back()) {
+            return &text[first.start..last.end];
+        }
+
+        match &self.next {
+            Some(next) => &text[next.start..],
+            None => self.tokens.remaining_text(),
+        }
+    }
+
+    /// Returns the bounds on the length in bytes of the remaining output,
+    /// e.g. for preallocating a buffer. Unlike [`size_hint()`], which is
+    /// the bounds on the number of remaining string slices.
+    ///
+    /// The lower bound is the length of the string slices, which have
+    /// already been determined due to lookahead. The upper bound is the
+    /// length of the remaining input, as the output is never longer than
+    /// the input, and is always `Some`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use jsonc_to_json::jsonc_to_json_iter;
+    /// let jsonc = "[1, /* Comment */ 2,]";
+    ///
+    /// let mut iter = jsonc_to_json_iter(jsonc);
+    /// assert_eq!(iter.remaining_bytes_hint(), (0, Some(21)));
+    ///
+    /// assert_eq!(iter.next(), Some("[1, "));
+    /// let (lower, upper) = iter.remaining_bytes_hint();
+    /// assert!((lower <= 3) && (upper >= Some(3)));
+    ///
+    /// iter.next_back();
+    /// assert_eq!(iter.remaining_bytes_hint(), (2, Some(2)));
+    /// ```
+    ///
+    /// [`size_hint()`]: Iterator::size_hint
+    #[must_use]
+    #[inline]
+    pub fn remaining_bytes_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.tokens.remaining_text().len();
+        let next = self.next.as_ref().map_or(0, Range::len);
+        let back = self.back.iter().map(Range::len).sum::<usize>();
+        (next + back, Some(remaining + next + back))
+    }
+
+    /// Returns an upper bound of the length in bytes of the
+    /// remaining output.
+    #[inline]
+    fn remaining_len(&self) -> usize {
+        let (_lower, upper) = self.remaining_bytes_hint();
+        upper.unwrap_or(0)
+    }
+}
+
+impl fmt::Display for JsonCToJsonIter<'_> {
+    /// Writes all remaining string slices, without allocating
+    /// an intermediate [`String`].
+    ///
+    /// The iterator itself is not advanced, i.e. the remaining string
+    /// slices are written from a clone of the iterator.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use jsonc_to_json::jsonc_to_json_iter;
+    /// let jsonc = r#"{foo}/**/[1,2,3,,]"bar""#;
+    ///
+    /// let iter = jsonc_to_json_iter(jsonc);
+    /// assert_eq!(format!("<{}>", iter), "<{foo}[1,2,3]\"bar\">");
+    /// assert_eq!(iter.collect::<String>(), "{foo}[1,2,3]\"bar\"");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for part in self.clone() {
+            f.write_str(part)?;
+        }
+        Ok(())
+    }
+}
+
+impl From<JsonCToJsonIter<'_>> for String {
+    /// Collects all remaining string slices into a new [`String`].
+    ///
+    /// See [`JsonCToJsonIter::collect_into()`] for more information.
+    #[inline]
+    fn from(iter: JsonCToJsonIter<'_>) -> Self {
+        let mut json = String::new();
+        iter.collect_into(&mut json);
+        json
+    }
+}
+
+impl From<JsonCToJsonIter<'_>> for Vec<u8> {
+    /// Collects all remaining string slices as bytes into a new [`Vec`].
+    ///
+    /// Like [`JsonCToJsonIter::collect_into()`], enough capacity is
+    /// reserved upfront, such that no reallocations occur.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use jsonc_to_json::jsonc_to_json_iter;
+    /// let jsonc = r#"{foo}/**/[1,2,3,,]"bar""#;
+    ///
+    /// let json = Vec::from(jsonc_to_json_iter(jsonc));
+    /// assert_eq!(json, b"{foo}[1,2,3]\"bar\"");
+    /// ```
+    fn from(iter: JsonCToJsonIter<'_>) -> Self {
+        let mut json = Vec::with_capacity(iter.remaining_len());
+        for part in iter {
+            json.extend_from_slice(part.as_bytes());
+        }
+        json
+    }
+}
+
+/// Part of the input removed by [`JsonCToJsonIter`].
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+enum Removed {
+    LineComment,
+    BlockComment,
+    /// `next_comma` is the start of the following comma, if the
+    /// trailing comma was removed due to being followed by a comma.
+    TrailingComma {
+        next_comma: Option<usize>,
+    },
+}
+
 trait JsonCToJsonExt<'jsonc> {
-    fn next_token(&mut self) -> Option<(JsonCToken, &'jsonc str)>;
-    fn next_valid_json_token(&mut self) -> Option<Range<usize>>;
+    fn next_valid_json_token<F>(
+        &mut self,
+        flags: StripFlags,
+        on_removed: &mut F,
+    ) -> Option<Range<usize>>
+    where
+        F: FnMut(Removed, Range<usize>);
 }
 
 impl<'jsonc, I> JsonCToJsonExt<'jsonc> for I
 where
-    I: Iterator<Item = (JsonCToken, TokenSpan<'jsonc>)>,
+    I: Iterator<Item = (LexerToken, TokenSpan<'jsonc>)>,
     I: Clone,
 {
     #[inline]
-    fn next_token(&mut self) -> Option<(JsonCToken, &'jsonc str)> {
-        let (tok, span) = self.next()?;
-        Some((tok, span.as_str()))
-    }
-
-    fn next_valid_json_token(&mut self) -> Option<Range<usize>> {
+    fn next_valid_json_token<F>(
+        &mut self,
+        flags: StripFlags,
+        on_removed: &mut F,
+    ) -> Option<Range<usize>>
+    where
+        F: FnMut(Removed, Range<usize>),
+    {
         loop {
             let (tok, span) = self.next()?;
             let s = span.as_str();
 
             match tok {
-                JsonCToken::Space => {}
-                JsonCToken::LineComment | JsonCToken::BlockComment => continue,
-                JsonCToken::Punct if s == "," => {
+                LexerToken::Space => {}
+                LexerToken::LineComment if flags.contains(StripFlags::STRIP_LINE_COMMENTS) => {
+                    on_removed(Removed::LineComment, span.range());
+                    continue;
+                }
+                LexerToken::BlockComment if flags.contains(StripFlags::STRIP_BLOCK_COMMENTS) => {
+                    on_removed(Removed::BlockComment, span.range());
+                    continue;
+                }
+                LexerToken::LineComment | LexerToken::BlockComment => {}
+                LexerToken::Punct
+                    if (s == ",") && flags.contains(StripFlags::STRIP_TRAILING_COMMAS) =>
+                {
                     let mut iter = self.clone().filter(|(tok, _span)| {
                         !matches!(
                             tok,
-                            JsonCToken::Space | JsonCToken::LineComment | JsonCToken::BlockComment
+                            LexerToken::Space | LexerToken::LineComment | LexerToken::BlockComment
                         )
                     });
 
-                    let (tok, s) = match iter.next_token() {
-                        Some((tok, s)) => (tok, s),
-                        None => continue,
+                    let (tok, next_span) = match iter.next() {
+                        Some((tok, next_span)) => (tok, next_span),
+                        None => {
+                            let removed = Removed::TrailingComma { next_comma: None };
+                            on_removed(removed, span.range());
+                            continue;
+                        }
                     };
+                    let s = next_span.as_str();
 
                     match tok {
-                        JsonCToken::Punct if s == "," => continue,
-                        JsonCToken::Delim => continue,
-                        JsonCToken::String
-                        | JsonCToken::Number
-                        | JsonCToken::Null
-                        | JsonCToken::True
-                        | JsonCToken::False
-                        | JsonCToken::Punct
-                        | JsonCToken::Unknown => {}
-                        JsonCToken::Space | JsonCToken::LineComment | JsonCToken::BlockComment => {
+                        LexerToken::Punct if s == "," => {
+                            let next_comma = Some(next_span.start());
+                            on_removed(Removed::TrailingComma { next_comma }, span.range());
+                            continue;
+                        }
+                        LexerToken::Delim if (s == "}") || (s == "]") => {
+                            let removed = Removed::TrailingComma { next_comma: None };
+                            on_removed(removed, span.range());
+                            continue;
+                        }
+                        LexerToken::String
+                        | LexerToken::Number
+                        | LexerToken::Null
+                        | LexerToken::True
+                        | LexerToken::False
+                        | LexerToken::Punct
+                        | LexerToken::Delim
+                        | LexerToken::Unknown => {}
+                        LexerToken::Space | LexerToken::LineComment | LexerToken::BlockComment => {
                             unreachable!()
                         }
                     }
                 }
-                JsonCToken::String
-                | JsonCToken::Number
-                | JsonCToken::Null
-                | JsonCToken::True
-                | JsonCToken::False
-                | JsonCToken::Punct
-                | JsonCToken::Delim
-                | JsonCToken::Unknown => {}
+                LexerToken::String
+                | LexerToken::Number
+                | LexerToken::Null
+                | LexerToken::True
+                | LexerToken::False
+                | LexerToken::Punct
+                | LexerToken::Delim
+                | LexerToken::Unknown => {}
             }
 
             return Some(span.range());
@@ -421,6 +1479,134 @@ mod tests {
         assert_jsonc_to_json!(jsonc, Cow::Owned(json.to_owned()));
     }
 
+    #[test]
+    fn test_consecutive_line_comments_before_delim() {
+        let owned = |json: &str| Cow::<str>::Owned(json.to_owned());
+
+        assert_jsonc_to_json!("{\"a\":1,\n//c1\n//c2\n}", owned("{\"a\":1\n\n\n}"));
+        assert_jsonc_to_json!(
+            "{\"a\":1,\n//c1\n//c2\n\"b\":2}",
+            owned("{\"a\":1,\n\n\n\"b\":2}")
+        );
+        assert_jsonc_to_json!("[1,//c1\n//c2\n2,//c3\n//c4\n]", owned("[1,\n\n2\n\n]"));
+        assert_jsonc_to_json!("{\"a\":1,/*c1*//*c2*/}", owned("{\"a\":1}"));
+    }
+
+    #[test]
+    fn test_comma_before_opening_delim() {
+        let owned = |json: &str| Cow::<str>::Owned(json.to_owned());
+
+        assert_jsonc_to_json!("[[1],[2]]", Cow::Borrowed("[[1],[2]]"));
+        assert_jsonc_to_json!("[{\"a\":1},/*c*/{\"b\":2}]", owned("[{\"a\":1},{\"b\":2}]"));
+        assert_jsonc_to_json!("[[1],//c\n[2],]", owned("[[1],\n[2]]"));
+        assert_jsonc_to_json!("{\"a\":[1],,\"b\":{}}", owned("{\"a\":[1],\"b\":{}}"));
+    }
+
+    #[test]
+    fn test_capacity() {
+        let jsonc = "[1, /* Comment */ 2,]";
+
+        match jsonc_to_json(jsonc) {
+            Cow::Owned(json) => assert!(json.capacity() >= jsonc.len()),
+            Cow::Borrowed(_) => panic!("expected owned"),
+        }
+
+        let json = jsonc_to_json_with_capacity(jsonc, 64);
+        assert_eq!(json, "[1,  2]");
+        assert!(json.capacity() >= 64);
+
+        let json = jsonc_to_json_with_capacity("[1, 2]", 0);
+        assert_eq!(json, "[1, 2]");
+    }
+
+    #[test]
+    fn test_in_place() {
+        let inputs = [
+            "",
+            "[1, 2]",
+            "[1, 2] // Comment",
+            "{\"æ\": [1, /* ø */ \"日本語\",,], // Comment\n}",
+            "[1,//c1\n//c2\n2,//c3\n//c4\n]",
+            "/* unterminated",
+        ];
+        for jsonc in inputs {
+            let expected = jsonc_to_json(jsonc);
+
+            let mut json = String::from(jsonc);
+            let capacity = json.capacity();
+            let modified = jsonc_to_json_in_place(&mut json);
+
+            assert_eq!(json, expected, "{:?}", jsonc);
+            assert_eq!(modified, json.len() != jsonc.len());
+            assert_eq!(json.capacity(), capacity);
+        }
+    }
+
+    #[test]
+    fn test_schema_property_kept() {
+        let jsonc = r#"{
+            // Schema used by editors
+            "$schema": "https://json.schemastore.org/tsconfig", /* Comment */
+            "compilerOptions": {"strict": true,},
+        }"#;
+        let expected = r#"{
+            
+            "$schema": "https://json.schemastore.org/tsconfig", 
+            "compilerOptions": {"strict": true}
+        }"#;
+
+        assert_eq!(jsonc_to_json(jsonc), expected);
+        assert_eq!(jsonc_to_json_strict(jsonc).unwrap(), expected);
+        assert_eq!(
+            jsonc_to_json_with_config(jsonc, &JsonCConfig::new()).unwrap(),
+            expected
+        );
+        for flags in [StripFlags::NONE, StripFlags::STRIP_TRAILING_COMMAS] {
+            let json = jsonc_to_json_selective(jsonc, flags);
+            assert!(json.contains(r#""$schema": "https://json.schemastore.org/tsconfig""#));
+        }
+    }
+
+    #[test]
+    fn test_selective() {
+        let jsonc = "{\"a\": [1, /* Block */ 2,,], // Line\n}";
+
+        assert_eq!(
+            jsonc_to_json_selective(jsonc, StripFlags::ALL),
+            jsonc_to_json(jsonc)
+        );
+        assert_eq!(jsonc_to_json_selective(jsonc, StripFlags::NONE), jsonc);
+        assert_eq!(
+            jsonc_to_json_selective(jsonc, StripFlags::STRIP_LINE_COMMENTS),
+            "{\"a\": [1, /* Block */ 2,,], \n}"
+        );
+        assert_eq!(
+            jsonc_to_json_selective(jsonc, StripFlags::STRIP_BLOCK_COMMENTS),
+            "{\"a\": [1,  2,,], // Line\n}"
+        );
+        assert_eq!(
+            jsonc_to_json_selective(jsonc, StripFlags::STRIP_TRAILING_COMMAS),
+            "{\"a\": [1, /* Block */ 2] // Line\n}"
+        );
+    }
+
+    #[test]
+    fn test_iter_into_string() {
+        let jsonc = r#"{foo}/**/[1,2,3,,]"bar""#;
+
+        let mut iter = jsonc_to_json_iter(jsonc);
+        assert_eq!(iter.next(), Some("{foo}"));
+
+        let json: String = iter.into();
+        assert_eq!(json, "[1,2,3]\"bar\"");
+    }
+
+    #[test]
+    fn test_iter_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<JsonCToJsonIter<'_>>();
+    }
+
     #[test]
     fn test_iter() {
         let jsonc = r#"{foo}/**/[1,2,3,,]"bar""#;
@@ -431,4 +1617,251 @@ mod tests {
         assert_eq!(iter.next(), Some("]\"bar\""));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn test_iter_clone() {
+        let jsonc = "{\"a\": [1, /* Comment */ 2,], // Comment\n\"b\": 3,}";
+        let parts = jsonc_to_json_iter(jsonc).collect::<Vec<_>>();
+        assert!(parts.len() > 2);
+
+        let iter = jsonc_to_json_iter(jsonc);
+        assert_eq!(iter.clone(), jsonc_to_json_iter(jsonc));
+        assert_eq!(iter.clone().collect::<Vec<_>>(), parts);
+
+        for i in 0..=parts.len() {
+            let mut iter = jsonc_to_json_iter(jsonc);
+            iter.by_ref().take(i).for_each(drop);
+
+            let mut clone = iter.clone();
+            assert_eq!(clone.current_position(), iter.current_position());
+
+            let remaining = parts[i..].concat().len();
+            let (lower, upper) = iter.remaining_bytes_hint();
+            assert!((lower <= remaining) && (upper >= Some(remaining)));
+
+            // Advancing the clone must not affect the original
+            assert_eq!(clone.by_ref().collect::<Vec<_>>(), parts[i..]);
+            assert_eq!(clone.next(), None);
+            assert_eq!(iter.collect::<Vec<_>>(), parts[i..]);
+        }
+
+        let mut iter = jsonc_to_json_iter(jsonc);
+        iter.by_ref().for_each(drop);
+        assert_eq!(iter.clone().next(), None);
+        assert_eq!(iter.clone().next_back(), None);
+
+        let iter = jsonc_to_json_iter(jsonc);
+        assert_eq!((&iter).into_iter().collect::<Vec<_>>(), parts);
+        assert_eq!(iter, jsonc_to_json_iter(jsonc));
+    }
+
+    #[test]
+    fn test_iter_fused() {
+        let inputs = ["", "[1, 2]", "[1, /* Comment */ 2,] // Comment", "[1, 2,"];
+        for jsonc in inputs {
+            let mut iter = jsonc_to_json_iter(jsonc);
+            iter.by_ref().for_each(drop);
+
+            assert!(iter.next.is_none(), "{:?}", jsonc);
+            assert!(iter.back.is_empty(), "{:?}", jsonc);
+            assert!(iter.tokens.clone().next().is_none(), "{:?}", jsonc);
+
+            for _ in 0..1000 {
+                assert_eq!(iter.next(), None, "{:?}", jsonc);
+            }
+            assert_eq!(iter.next_back(), None, "{:?}", jsonc);
+
+            let mut iter = jsonc_to_json_iter(jsonc);
+            while iter.next_back().is_some() {}
+            for _ in 0..1000 {
+                assert_eq!(iter.next(), None, "{:?}", jsonc);
+                assert_eq!(iter.next_back(), None, "{:?}", jsonc);
+            }
+        }
+    }
+
+    #[test]
+    fn test_iter_count() {
+        let inputs = [
+            "",
+            "[1, 2]",
+            "// Comment",
+            r#"{foo}/**/[1,2,3,,]"bar""#,
+            "[1, /* a */ /* b */ 2,] // c\n",
+        ];
+        for jsonc in inputs {
+            let expected = jsonc_to_json_iter(jsonc).collect::<Vec<_>>().len();
+            assert_eq!(jsonc_to_json_iter(jsonc).count(), expected, "{:?}", jsonc);
+
+            let mut iter = jsonc_to_json_iter(jsonc);
+            if iter.next().is_some() {
+                assert_eq!(iter.clone().count(), expected - 1, "{:?}", jsonc);
+                iter.next_back();
+                assert_eq!(iter.count(), expected.saturating_sub(2), "{:?}", jsonc);
+            }
+        }
+    }
+
+    #[test]
+    fn test_iter_fold() {
+        let inputs = [
+            "",
+            "[1, 2]",
+            "// Comment",
+            r#"{foo}/**/[1,2,3,,]"bar""#,
+            "[1, /* a */ /* b */ 2,] // c\n",
+        ];
+        for jsonc in inputs {
+            let mut iter = jsonc_to_json_iter(jsonc);
+            let expected = std::iter::from_fn(|| iter.next()).collect::<Vec<_>>();
+
+            let parts = jsonc_to_json_iter(jsonc).fold(Vec::new(), |mut parts, part| {
+                parts.push(part);
+                parts
+            });
+            assert_eq!(parts, expected, "{:?}", jsonc);
+            assert_eq!(jsonc_to_json_iter(jsonc).last(), expected.last().copied());
+
+            let mut iter = jsonc_to_json_iter(jsonc);
+            if iter.next().is_some() {
+                let parts = iter.clone().fold(Vec::new(), |mut parts, part| {
+                    parts.push(part);
+                    parts
+                });
+                assert_eq!(parts, expected[1..], "{:?}", jsonc);
+
+                iter.next_back();
+                let expected = &expected[1..expected.len().max(2) - 1];
+                assert_eq!(iter.clone().last(), expected.last().copied());
+
+                let parts = iter.fold(Vec::new(), |mut parts, part| {
+                    parts.push(part);
+                    parts
+                });
+                assert_eq!(parts, expected, "{:?}", jsonc);
+            }
+        }
+    }
+
+    #[test]
+    fn test_into_parts() {
+        let inputs = [
+            "",
+            "[1, 2]",
+            "// Comment",
+            "[1, /* a */ /* b */ 2,,] // c\n",
+            "{\"a\": [1,],}",
+        ];
+        for jsonc in inputs {
+            let (kept, removed) = jsonc_to_json_iter(jsonc).into_parts();
+
+            let json = kept.iter().map(|r| &jsonc[r.clone()]).collect::<String>();
+            assert_eq!(json, jsonc_to_json(jsonc), "{:?}", jsonc);
+
+            let parts = jsonc_to_json_iter(jsonc).collect_parts();
+            assert!(parts
+                .iter()
+                .zip(&kept)
+                .all(|(part, r)| *part == &jsonc[r.clone()]));
+            assert_eq!(parts.len(), kept.len());
+
+            let mut ranges = kept.iter().chain(&removed).cloned().collect::<Vec<_>>();
+            ranges.sort_by_key(|r| r.start);
+            let mut end = 0;
+            for r in ranges {
+                assert_eq!(r.start, end, "{:?}", jsonc);
+                end = r.end;
+            }
+            assert_eq!(end, jsonc.len(), "{:?}", jsonc);
+        }
+    }
+
+    #[test]
+    fn test_current_position() {
+        let jsonc = "{\"æ\": /* a\nb */ [1,\r\n 2,],\n\"日本\": 3} // c";
+
+        let mut iter = jsonc_to_json_iter(jsonc);
+        let mut end = 0;
+        while let Some(part) = iter.next() {
+            end = (part.as_ptr() as usize) - (jsonc.as_ptr() as usize) + part.len();
+
+            let before = &jsonc[..end];
+            let line = before.matches('\n').count() + 1;
+            let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+            let column = before[line_start..].chars().count() + 1;
+            assert_eq!(iter.current_position(), (line, column), "{:?}", part);
+        }
+        assert_eq!(end, jsonc.len() - "// c".len());
+    }
+
+    #[test]
+    fn test_iter_into_vec() {
+        let jsonc = "{\"æ\": [1, /* ø */ 2,], // 日本語\n}";
+
+        let json = Vec::from(jsonc_to_json_iter(jsonc));
+        assert_eq!(json, jsonc_to_json(jsonc).as_bytes());
+        assert!(json.capacity() >= jsonc.len());
+
+        let mut iter = jsonc_to_json_iter(jsonc);
+        iter.next();
+        iter.next_back();
+        let json = Vec::from(iter.clone());
+        assert_eq!(json, String::from(iter).into_bytes());
+    }
+
+    #[test]
+    fn test_iter_rev() {
+        let jsonc = r#"{foo}/**/[1,2,3,,]"bar"// Line Comment
+        /* Block Comment */ [1, 2, /* 3 */ 4,]"#;
+
+        let mut parts = jsonc_to_json_iter(jsonc).collect::<Vec<_>>();
+        parts.reverse();
+        assert_eq!(jsonc_to_json_iter(jsonc).rev().collect::<Vec<_>>(), parts);
+
+        let mut iter = jsonc_to_json_iter(jsonc);
+        assert_eq!(iter.next(), Some("{foo}"));
+        assert_eq!(iter.next_back(), Some("]"));
+        assert_eq!(iter.next_back(), Some(" 4"));
+        assert_eq!(String::from(iter), "[1,2,3]\"bar\"\n         [1, 2, ");
+    }
+
+    #[test]
+    fn test_as_remaining_str() {
+        let jsonc = "[1] // Comment\n[2,] [3]";
+
+        let mut iter = jsonc_to_json_iter(jsonc);
+        assert_eq!(iter.next(), Some("[1] "));
+        assert_eq!(iter.as_remaining_str(), "\n[2,] [3]");
+        assert_eq!(iter.next_back(), Some("] [3]"));
+        assert_eq!(iter.as_remaining_str(), "\n[2");
+
+        iter.by_ref().for_each(drop);
+        assert_eq!(iter.as_remaining_str(), "");
+    }
+
+    #[test]
+    fn test_iter_eq() {
+        use std::collections::hash_map::DefaultHasher;
+
+        fn hash(iter: &JsonCToJsonIter<'_>) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            iter.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let a = String::from("[1] /* Comment */ [2,]");
+        let b = String::from("[0] /* Other */ [2,]");
+
+        let mut iter_a = jsonc_to_json_iter(&a);
+        let mut iter_b = jsonc_to_json_iter(&b);
+        assert_ne!(iter_a, iter_b);
+
+        iter_a.next();
+        iter_b.next();
+        assert_eq!(iter_a, iter_b);
+        assert_eq!(hash(&iter_a), hash(&iter_b));
+
+        let iter_c = JsonCToJsonIter::with_flags(&b, StripFlags::NONE);
+        assert_ne!(jsonc_to_json_iter(&b), iter_c);
+    }
 }
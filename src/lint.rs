@@ -0,0 +1,391 @@
+use std::ops::Range;
+
+use any_lexer::JsonCToken;
+
+use crate::tokens::JsonCTokens;
+use crate::{jsonc_extract_comments, CommentKind};
+
+/// Comment which looks like commented-out [JSON],
+/// see [`find_commented_out_json()`].
+///
+/// [JSON]: https://www.json.org/json-en.html
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct CommentedOutJson<'jsonc> {
+    /// Whether the region consists of line comments or a block comment.
+    pub kind: CommentKind,
+    /// The commented-out region, including `//`, `/*`, and `*/`,
+    /// i.e. the same as `&jsonc[range]`.
+    pub source: &'jsonc str,
+    /// Byte range of the region, which is either a single block
+    /// comment, or consecutive line comments.
+    pub range: Range<usize>,
+}
+
+/// Returns the comments in `jsonc`, which look like commented-out
+/// [JSON], e.g. for flagging them during code review.
+///
+/// Consecutive line comments, i.e. only separated by a single line
+/// break, are checked together as one region. A region is considered
+/// commented-out [JSON], if its text only consists of [JSON] tokens,
+/// contains a `"key":` or a bracket, and all brackets are balanced.
+///
+/// **Note:** This is a heuristic, i.e. the text is not parsed, and
+/// e.g. `[1, 2]` in a comment explaining a value is also returned.
+///
+/// _Requires the `lint` feature._
+///
+/// # Example
+///
+/// ```rust
+/// use jsonc_to_json::{find_commented_out_json, CommentKind};
+///
+/// let jsonc = r#"{
+///     // Size of a tab in spaces
+///     "tabSize": 4,
+///     // "theme": "dark",
+///     // "colors": [1, 2],
+///     /* "font": { "size": 12 } */
+/// }"#;
+///
+/// let found = find_commented_out_json(jsonc);
+/// assert_eq!(found.len(), 2);
+///
+/// assert_eq!(found[0].kind, CommentKind::Line);
+/// assert_eq!(
+///     found[0].source,
+///     "// \"theme\": \"dark\",\n    // \"colors\": [1, 2],"
+/// );
+///
+/// assert_eq!(found[1].kind, CommentKind::Block);
+/// assert_eq!(found[1].source, "/* \"font\": { \"size\": 12 } */");
+/// ```
+///
+/// [JSON]: https://www.json.org/json-en.html
+#[must_use]
+pub fn find_commented_out_json(jsonc: &str) -> Vec<CommentedOutJson<'_>> {
+    let mut found = Vec::new();
+
+    // Range of the current region of consecutive line comments
+    let mut lines: Option<Range<usize>> = None;
+
+    let mut check = |kind, range: Range<usize>| {
+        let source = &jsonc[range.clone()];
+        let text = match kind {
+            CommentKind::Line => source
+                .lines()
+                .map(|line| line.trim_start().strip_prefix("//").unwrap_or(line))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            // Unterminated block comments extend to the end of the input
+            CommentKind::Block => {
+                let text = &source[2..];
+                text.strip_suffix("*/").unwrap_or(text).to_owned()
+            }
+        };
+
+        if looks_like_json(&text) {
+            found.push(CommentedOutJson {
+                kind,
+                source,
+                range,
+            });
+        }
+    };
+
+    for (tok, span) in JsonCTokens::new(jsonc) {
+        match tok {
+            JsonCToken::LineComment => {
+                lines = match lines {
+                    Some(lines) => Some(lines.start..span.end()),
+                    None => Some(span.range()),
+                };
+                continue;
+            }
+            JsonCToken::Space if span.as_str().matches('\n').count() <= 1 => continue,
+            _ => {}
+        }
+
+        if let Some(lines) = lines.take() {
+            check(CommentKind::Line, lines);
+        }
+
+        if tok == JsonCToken::BlockComment {
+            check(CommentKind::Block, span.range());
+        }
+    }
+
+    if let Some(lines) = lines {
+        check(CommentKind::Line, lines);
+    }
+
+    found
+}
+
+/// Returns `true` if `text` only consists of [JSON] tokens, contains
+/// a `"key":` or a bracket, and all brackets are balanced.
+///
+/// [JSON]: https://www.json.org/json-en.html
+fn looks_like_json(text: &str) -> bool {
+    let mut depth = 0_usize;
+    let mut structural = false;
+    let mut prev = None;
+
+    for (tok, span) in JsonCTokens::new(text) {
+        match tok {
+            JsonCToken::Space | JsonCToken::LineComment | JsonCToken::BlockComment => continue,
+            JsonCToken::Unknown => return false,
+            JsonCToken::Delim => {
+                depth = match span.as_str() {
+                    "{" | "[" => depth + 1,
+                    _ => match depth.checked_sub(1) {
+                        Some(depth) => depth,
+                        None => return false,
+                    },
+                };
+                structural = true;
+            }
+            JsonCToken::Punct if span.as_str() == ":" => {
+                if prev != Some(JsonCToken::String) {
+                    return false;
+                }
+                structural = true;
+            }
+            _ => {}
+        }
+        prev = Some(tok);
+    }
+
+    structural && (depth == 0)
+}
+
+/// Kind of annotation found in a comment, see [`jsonc_lint()`].
+#[non_exhaustive]
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum LintKind {
+    /// `TODO` in a comment.
+    Todo,
+    /// `FIXME` in a comment.
+    Fixme,
+    /// `HACK` in a comment.
+    Hack,
+    /// IDE inspection suppression, i.e. a comment
+    /// starting with `noinspection`.
+    NoInspection,
+}
+
+impl LintKind {
+    const ALL: [Self; 4] = [Self::Todo, Self::Fixme, Self::Hack, Self::NoInspection];
+
+    /// Returns `true` if `text` of a comment, excluding `//`, `/*`,
+    /// and `*/`, contains this kind of annotation.
+    fn matches(self, text: &str) -> bool {
+        let word = match self {
+            Self::Todo => "TODO",
+            Self::Fixme => "FIXME",
+            Self::Hack => "HACK",
+            Self::NoInspection => return text.trim_start().starts_with("noinspection"),
+        };
+        text.split(|c: char| !c.is_ascii_alphanumeric() && (c != '_'))
+            .any(|w| w == word)
+    }
+}
+
+/// Annotation found in a comment, see [`jsonc_lint()`].
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct LintWarning<'jsonc> {
+    /// Kind of annotation.
+    pub kind: LintKind,
+    /// Byte range of the whole comment, including delimiters.
+    pub range: Range<usize>,
+    /// The text of the comment, excluding `//`, `/*`, and `*/`.
+    pub text: &'jsonc str,
+}
+
+/// Configuration for [`jsonc_lint_with_config()`].
+///
+/// By default all kinds of annotations are checked.
+///
+/// _Requires the `lint` feature._
+#[derive(Clone, Debug)]
+pub struct LintConfig {
+    enabled: Vec<LintKind>,
+}
+
+impl LintConfig {
+    /// Creates a new `LintConfig`, which checks all kinds of annotations.
+    #[must_use]
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            enabled: LintKind::ALL.to_vec(),
+        }
+    }
+
+    /// Specifies whether to check for `kind` of annotations.
+    #[must_use]
+    pub fn check(mut self, kind: LintKind, enabled: bool) -> Self {
+        self.enabled.retain(|&k| k != kind);
+        if enabled {
+            self.enabled.push(kind);
+        }
+        self
+    }
+}
+
+impl Default for LintConfig {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns annotations in comments in `jsonc`, which should probably
+/// not appear in production configs, i.e. `TODO`, `FIXME`, `HACK`,
+/// and `noinspection`.
+///
+/// `TODO`, `FIXME`, and `HACK` must be uppercase and a separate word.
+/// `noinspection` must be at the start of the comment, as written by
+/// IDEs, e.g. `//noinspection JsonStandardCompliance`.
+///
+/// Only comments are checked, and the conversion is unaffected.
+/// A comment containing multiple kinds of annotations results in
+/// a warning for each kind.
+///
+/// See [`jsonc_lint_with_config()`] for only checking some kinds.
+///
+/// _Requires the `lint` feature._
+///
+/// # Example
+///
+/// ```rust
+/// use jsonc_to_json::{jsonc_lint, LintKind};
+///
+/// let jsonc = r#"{
+///     //noinspection JsonStandardCompliance
+///     "a": 1, /* TODO: Remove */
+///     // Todo list
+///     "todos": [],
+/// }"#;
+///
+/// let warnings = jsonc_lint(jsonc);
+/// assert_eq!(warnings.len(), 2);
+///
+/// assert_eq!(warnings[0].kind, LintKind::NoInspection);
+/// assert_eq!(warnings[1].kind, LintKind::Todo);
+/// assert_eq!(warnings[1].text, " TODO: Remove ");
+/// assert_eq!(&jsonc[warnings[1].range.clone()], "/* TODO: Remove */");
+/// ```
+#[must_use]
+#[inline]
+pub fn jsonc_lint(jsonc: &str) -> Vec<LintWarning<'_>> {
+    jsonc_lint_with_config(jsonc, &LintConfig::new())
+}
+
+/// Same as [`jsonc_lint()`], but only checks the kinds of
+/// annotations enabled in `config`.
+///
+/// _Requires the `lint` feature._
+///
+/// # Example
+///
+/// ```rust
+/// use jsonc_to_json::{jsonc_lint_with_config, LintConfig, LintKind};
+///
+/// let jsonc = "[1, /* TODO */ 2] // FIXME";
+///
+/// let config = LintConfig::new().check(LintKind::Todo, false);
+///
+/// let warnings = jsonc_lint_with_config(jsonc, &config);
+/// assert_eq!(warnings.len(), 1);
+/// assert_eq!(warnings[0].kind, LintKind::Fixme);
+/// ```
+#[must_use]
+pub fn jsonc_lint_with_config<'jsonc>(
+    jsonc: &'jsonc str,
+    config: &LintConfig,
+) -> Vec<LintWarning<'jsonc>> {
+    let mut warnings = Vec::new();
+
+    for comment in jsonc_extract_comments(jsonc) {
+        for kind in LintKind::ALL {
+            if config.enabled.contains(&kind) && kind.matches(comment.text) {
+                warnings.push(LintWarning {
+                    kind,
+                    range: comment.range.clone(),
+                    text: comment.text,
+                });
+            }
+        }
+    }
+
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_looks_like_json() {
+        let json = [
+            "\"a\": 1,",
+            " [1, 2]",
+            "{\"a\": [true, null]}",
+            "\"a\": {\n\"b\": 1\n}",
+        ];
+        for text in json {
+            assert!(looks_like_json(text), "{:?}", text);
+        }
+
+        let not_json = [
+            "",
+            " Comment",
+            " 123",
+            " \"quoted\"",
+            " TODO: fix",
+            " [deprecated]",
+            " Use \"a\": 1 instead",
+            " [1, 2",
+            " 1, 2]",
+            " 1: 2",
+        ];
+        for text in not_json {
+            assert!(!looks_like_json(text), "{:?}", text);
+        }
+    }
+
+    #[test]
+    fn test_find_commented_out_json() {
+        let jsonc =
+            "[\n// 1,\n\n// [2, 3],\n4, // 5]\n/* {} */ /* Comment */ // {\n// \"a\": 1\n// }";
+        let found = find_commented_out_json(jsonc)
+            .into_iter()
+            .map(|found| found.source)
+            .collect::<Vec<_>>();
+        assert_eq!(found, ["// [2, 3],", "/* {} */", "// {\n// \"a\": 1\n// }"]);
+    }
+
+    #[test]
+    fn test_lint_kind_matches() {
+        let cases = [
+            ("TODO", Some(LintKind::Todo)),
+            (" TODO: fix", Some(LintKind::Todo)),
+            (" (FIXME)", Some(LintKind::Fixme)),
+            ("HACK!", Some(LintKind::Hack)),
+            (
+                "noinspection JsonStandardCompliance",
+                Some(LintKind::NoInspection),
+            ),
+            (" noinspection", Some(LintKind::NoInspection)),
+            (" todo", None),
+            (" TODOS", None),
+            (" TODO_LIST", None),
+            (" HACKED", None),
+            (" see noinspection", None),
+        ];
+        for (text, expected) in cases {
+            let found = LintKind::ALL.into_iter().find(|kind| kind.matches(text));
+            assert_eq!(found, expected, "{:?}", text);
+        }
+    }
+}
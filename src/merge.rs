@@ -0,0 +1,158 @@
+use serde_json::Value;
+
+use crate::{jsonc_to_json_iter, jsonc_to_json_strict, JsonCError};
+
+/// Deep merges the [JSON with Comments] `overlay` into `base`,
+/// returning the merged [JSON].
+///
+/// Both `base` and `overlay` are converted using [`jsonc_to_json_strict()`]
+/// and then parsed using [`serde_json`]. Objects are merged recursively,
+/// with values in `overlay` taking precedence. All other values, including
+/// arrays, in `overlay` replace the value in `base`. This is the same
+/// semantics as VS Code uses when merging settings.
+///
+/// The output is serialized without whitespace, and object keys are
+/// ordered according to [`serde_json::Map`].
+///
+/// Returns [`JsonCError`] if either input is not valid [JSON with Comments],
+/// or if it contains values that [`serde_json`] cannot represent.
+///
+/// _Requires the `merge` feature._
+///
+/// # Example
+///
+/// ```rust
+/// use jsonc_to_json::jsonc_merge;
+///
+/// let base = r#"{
+///     // Defaults
+///     "editor": {"tabSize": 4, "rulers": [80, 100]},
+///     "theme": "light",
+/// }"#;
+/// let overlay = r#"{
+///     "editor": {"rulers": [120],}, // Replaces the array
+///     "theme": "dark",
+/// }"#;
+///
+/// let json = jsonc_merge(base, overlay)?;
+/// assert_eq!(json, r#"{"editor":{"rulers":[120],"tabSize":4},"theme":"dark"}"#);
+/// # Ok::<(), jsonc_to_json::JsonCError>(())
+/// ```
+///
+/// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+/// [JSON]: https://www.json.org/json-en.html
+/// [`serde_json`]: https://docs.rs/serde_json
+/// [`serde_json::Map`]: https://docs.rs/serde_json/latest/serde_json/struct.Map.html
+pub fn jsonc_merge(base: &str, overlay: &str) -> Result<String, JsonCError> {
+    let mut base_value = parse(base)?;
+    let overlay_value = parse(overlay)?;
+
+    merge(&mut base_value, overlay_value);
+
+    Ok(base_value.to_string())
+}
+
+fn parse(jsonc: &str) -> Result<Value, JsonCError> {
+    let json = jsonc_to_json_strict(jsonc)?;
+    serde_json::from_str(&json).map_err(|err| {
+        let output_offset = number_start(&json, offset_of(&json, err.line(), err.column()));
+        JsonCError::Unrepresentable {
+            byte_offset: input_offset(jsonc, output_offset),
+        }
+    })
+}
+
+fn merge(base: &mut Value, overlay: Value) {
+    match (base, overlay) {
+        (Value::Object(base), Value::Object(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(base) => merge(base, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+/// Returns the byte offset of the 1-based `line` and the
+/// 1-based byte `column` in `s`.
+/// byte-based `column` in `s`.
+fn offset_of(s: &str, line: usize, column: usize) -> usize {
+    let line_start = s
+        .split_inclusive('\n')
+        .take(line.saturating_sub(1))
+        .map(str::len)
+        .sum::<usize>();
+    (line_start + column.saturating_sub(1)).min(s.len())
+}
+
+/// If `offset` is within a number in `json`, then the offset
+/// of the start of that number is returned, otherwise `offset`.
+///
+/// `serde_json` reports out of range numbers at their last
+/// character, while [`JsonCError::Unrepresentable`] points
+/// at the value itself.
+fn number_start(json: &str, offset: usize) -> usize {
+    let is_number = |b: &u8| b.is_ascii_digit() || matches!(b, b'-' | b'+' | b'.' | b'e' | b'E');
+    let bytes = json.as_bytes();
+    if !bytes.get(offset).is_some_and(is_number) {
+        return offset;
+    }
+    let len = bytes[..offset]
+        .iter()
+        .rev()
+        .take_while(|b| is_number(b))
+        .count();
+    offset - len
+}
+
+/// Maps a byte offset in the output of [`jsonc_to_json()`]
+/// to the corresponding byte offset in `jsonc`.
+///
+/// [`jsonc_to_json()`]: crate::jsonc_to_json
+fn input_offset(jsonc: &str, mut output_offset: usize) -> usize {
+    let mut end = 0;
+    for part in jsonc_to_json_iter(jsonc) {
+        let start = (part.as_ptr() as usize) - (jsonc.as_ptr() as usize);
+        if output_offset < part.len() {
+            return start + output_offset;
+        }
+        output_offset -= part.len();
+        end = start + part.len();
+    }
+    end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_non_objects() {
+        assert_eq!(jsonc_merge("{\"a\": 1}", "[1, 2,]"), Ok("[1,2]".to_owned()));
+        assert_eq!(jsonc_merge("[1]", "{\"a\": 1}"), Ok("{\"a\":1}".to_owned()));
+        assert_eq!(
+            jsonc_merge("{\"a\": {\"b\": 1}}", "{\"a\": null}"),
+            Ok("{\"a\":null}".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_merge_errors() {
+        assert_eq!(
+            jsonc_merge("{\"a\": 1}", "{\"a\" 1}"),
+            Err(JsonCError::UnexpectedToken { byte_offset: 5 })
+        );
+        assert_eq!(
+            jsonc_merge("/* Comment */ [1e400]", "{}"),
+            Err(JsonCError::Unrepresentable { byte_offset: 15 })
+        );
+        assert_eq!(
+            jsonc_merge("{}", "{\n  // Comment\n  \"a\": -1e400,\n}"),
+            Err(JsonCError::Unrepresentable { byte_offset: 22 })
+        );
+    }
+}
@@ -0,0 +1,38 @@
+/// Maps byte offsets in the output of a rewrite, e.g. of `Json5::to_jsonc()`
+/// or a [`Stage`], back into byte offsets in its input.
+///
+/// [`Stage`]: crate::Stage
+#[derive(Clone, Default, Debug)]
+pub(crate) struct OffsetMap {
+    /// `(output, input)` byte offsets of the start and end of each
+    /// rewritten part, ordered by their position.
+    checkpoints: Vec<(usize, usize)>,
+}
+
+impl OffsetMap {
+    /// Records that byte offset `output` in the output
+    /// corresponds to byte offset `input` in the input.
+    #[inline]
+    pub(crate) fn push(&mut self, output: usize, input: usize) {
+        self.checkpoints.push((output, input));
+    }
+
+    /// Returns the input byte offset corresponding to `offset` in the output.
+    ///
+    /// Offsets inside a rewritten part are clamped to the end of the part
+    /// in the input, e.g. after an escape sequence was shortened.
+    pub(crate) fn to_input(&self, offset: usize) -> usize {
+        let i = self
+            .checkpoints
+            .partition_point(|&(output, _)| output <= offset);
+        let Some(&(output, input)) = i.checked_sub(1).map(|i| &self.checkpoints[i]) else {
+            return offset;
+        };
+
+        let mapped = input + (offset - output);
+        match self.checkpoints.get(i) {
+            Some(&(_, next_input)) => mapped.min(next_input),
+            None => mapped,
+        }
+    }
+}
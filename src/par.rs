@@ -0,0 +1,65 @@
+use rayon::prelude::*;
+
+use crate::jsonc_to_json;
+
+/// Converts all `inputs` in parallel using [`rayon`], returning the
+/// resulting [JSON] in the same order as `inputs`.
+///
+/// Each input is converted using [`jsonc_to_json()`], see it for
+/// more information.
+///
+/// _Requires the `rayon` feature._
+///
+/// # Memory Usage
+///
+/// The conversion itself does not allocate any per-thread buffers.
+/// The [`JsonCToJsonIter`] used by [`jsonc_to_json()`] lives on the
+/// stack of the worker thread, and its buffer for [`next_back()`] is
+/// never allocated, as the input is only iterated forwards. The only
+/// allocations are the output [`String`]s, each with the capacity of
+/// its corresponding input. As such, peak memory usage is bounded by
+/// twice the total size of `inputs` plus the `Vec` holding the results,
+/// regardless of the number of threads.
+///
+/// # Example
+///
+/// ```rust
+/// use jsonc_to_json::jsonc_to_json_par_batch;
+///
+/// let inputs = ["[1, 2, 3,]", "{\"foo\": 1 /* Comment */}", "null // Comment"];
+///
+/// let json = jsonc_to_json_par_batch(&inputs);
+/// assert_eq!(json, ["[1, 2, 3]", "{\"foo\": 1 }", "null "]);
+/// ```
+///
+/// [`rayon`]: https://docs.rs/rayon
+/// [`JsonCToJsonIter`]: crate::JsonCToJsonIter
+/// [`next_back()`]: DoubleEndedIterator::next_back
+/// [JSON]: https://www.json.org/json-en.html
+#[must_use = "this returns the converted JSON; dropping it does nothing"]
+pub fn jsonc_to_json_par_batch(inputs: &[&str]) -> Vec<String> {
+    inputs
+        .par_iter()
+        .map(|jsonc| jsonc_to_json(jsonc).into_owned())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_par_batch_matches_sequential() {
+        let inputs = (0..1000)
+            .map(|i| format!("{{\"i\": [{}, {},,]}} // Line Comment", i, i * 2))
+            .collect::<Vec<_>>();
+        let inputs = inputs.iter().map(String::as_str).collect::<Vec<_>>();
+
+        let expected = inputs
+            .iter()
+            .map(|jsonc| jsonc_to_json(jsonc).into_owned())
+            .collect::<Vec<_>>();
+
+        assert_eq!(jsonc_to_json_par_batch(&inputs), expected);
+    }
+}
@@ -0,0 +1,339 @@
+use std::borrow::Cow;
+use std::env;
+use std::mem;
+
+use any_lexer::JsonCToken;
+
+use crate::offsets::OffsetMap;
+use crate::tokens::JsonCTokens;
+use crate::vars::substitute_vars_into;
+use crate::{JsonCError, JsonCToJsonIter, StripFlags, UnknownVar};
+
+/// Transformation applied by a [`JsonCPreprocessor`].
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+#[non_exhaustive]
+pub enum Stage {
+    /// Remove line comments and block comments.
+    StripComments,
+    /// Remove trailing commas.
+    StripTrailingCommas,
+    /// Substitute `${VAR}` references inside strings with the value
+    /// of the corresponding environment variable. References to
+    /// environment variables, which are not set or not valid unicode,
+    /// are handled according to the [`UnknownVar`].
+    ///
+    /// See [`jsonc_expand_vars_with()`] for more information.
+    ///
+    /// [`jsonc_expand_vars_with()`]: crate::jsonc_expand_vars_with
+    ExpandEnvVars(UnknownVar),
+    /// Turn all `\r\n` outside of strings into `\n`,
+    /// see [`LineEnding::Lf`].
+    ///
+    /// [`LineEnding::Lf`]: crate::LineEnding::Lf
+    NormalizeCrlf,
+}
+
+impl Stage {
+    /// Applies the stage to `jsonc`, appending the output to `out`.
+    /// Returns `false` if the stage did not change `jsonc`, in which
+    /// case the contents of `out` are unspecified.
+    ///
+    /// If `offsets` is `Some`, then it is filled with the mapping of byte
+    /// offsets in the output back into `jsonc`.
+    fn apply_into(
+        &self,
+        jsonc: &str,
+        out: &mut String,
+        mut offsets: Option<&mut OffsetMap>,
+    ) -> Result<bool, JsonCError> {
+        let flags = match *self {
+            Self::StripComments => StripFlags::STRIP_COMMENTS,
+            Self::StripTrailingCommas => StripFlags::STRIP_TRAILING_COMMAS,
+            Self::ExpandEnvVars(unknown) => {
+                let lookup = |name: &str| env::var(name).ok().map(Cow::Owned);
+                return substitute_vars_into(jsonc, lookup, unknown, out, offsets);
+            }
+            Self::NormalizeCrlf => {
+                // End of the part of `jsonc`, which has been copied into `out`
+                let mut copied = 0;
+                for (tok, span) in JsonCTokens::new(jsonc) {
+                    if tok != JsonCToken::Space {
+                        continue;
+                    }
+                    for (i, _) in span.as_str().match_indices("\r\n") {
+                        let cr = span.start() + i;
+                        push_part(out, &jsonc[copied..cr], copied, offsets.as_deref_mut());
+                        // Skip the `\r` and keep the `\n`
+                        copied = cr + 1;
+                    }
+                }
+                if copied == 0 {
+                    return Ok(false);
+                }
+                push_part(out, &jsonc[copied..], copied, offsets);
+                return Ok(true);
+            }
+        };
+
+        // Only removes, i.e. the output is shorter if anything is removed
+        let start = out.len();
+        for part in JsonCToJsonIter::with_flags(jsonc, flags) {
+            let input = (part.as_ptr() as usize) - (jsonc.as_ptr() as usize);
+            push_part(out, part, input, offsets.as_deref_mut());
+        }
+        Ok((out.len() - start) != jsonc.len())
+    }
+}
+
+/// Appends `part`, which starts at byte offset `input` in the input, to `out`.
+#[inline]
+fn push_part(out: &mut String, part: &str, input: usize, offsets: Option<&mut OffsetMap>) {
+    if let Some(offsets) = offsets {
+        offsets.push(out.len(), input);
+    }
+    out.push_str(part);
+}
+
+/// Applies a sequence of [`Stage`]s to [JSON with Comments], in the
+/// order they were added.
+///
+/// Stages write their output into one of two buffers, alternating
+/// between them, i.e. only two buffers are used regardless of the
+/// number of stages. If no stage changes the input, then the input
+/// is returned as is. Use [`process_buffered()`] to additionally reuse
+/// the buffers across calls.
+///
+/// The byte offsets of errors refer to the input, i.e. not to the
+/// output of the previous stage.
+///
+/// # Example
+///
+/// ```rust
+/// use jsonc_to_json::{JsonCPreprocessor, Stage, UnknownVar};
+///
+/// let preprocessor = JsonCPreprocessor::new()
+///     .stage(Stage::ExpandEnvVars(UnknownVar::Empty))
+///     .stage(Stage::StripComments)
+///     .stage(Stage::StripTrailingCommas)
+///     .stage(Stage::NormalizeCrlf);
+///
+/// let jsonc = "{\r\n  \"a\": \"${JSONC_TO_JSON_UNSET}\", // Comment\r\n}";
+///
+/// let json = preprocessor.process(jsonc)?;
+/// assert_eq!(json, "{\n  \"a\": \"\" \n}");
+/// # Ok::<(), jsonc_to_json::JsonCError>(())
+/// ```
+///
+/// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+/// [`process_buffered()`]: Self::process_buffered
+#[derive(Clone, Default, Debug)]
+pub struct JsonCPreprocessor {
+    stages: Vec<Stage>,
+    bufs: [String; 2],
+}
+
+impl JsonCPreprocessor {
+    /// Creates a new `JsonCPreprocessor` without any stages,
+    /// i.e. which returns the input as is.
+    #[must_use]
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a `stage`, which is applied after all previously added stages.
+    #[must_use]
+    #[inline]
+    pub fn stage(mut self, stage: Stage) -> Self {
+        self.stages.push(stage);
+        self
+    }
+
+    /// Returns the stages in the order they are applied.
+    #[inline]
+    pub fn stages(&self) -> &[Stage] {
+        &self.stages
+    }
+
+    /// Applies all stages to `jsonc` in order.
+    ///
+    /// Returns the first error returned by a stage,
+    /// e.g. [`JsonCError::UnknownVariable`].
+    pub fn process<'jsonc>(&self, jsonc: &'jsonc str) -> Result<Cow<'jsonc, str>, JsonCError> {
+        let mut bufs = [String::new(), String::new()];
+        Ok(match self.process_into(jsonc, &mut bufs)? {
+            Some(i) => Cow::Owned(mem::take(&mut bufs[i])),
+            None => Cow::Borrowed(jsonc),
+        })
+    }
+
+    /// Same as [`process()`], but the stages write into buffers owned by
+    /// the `JsonCPreprocessor`, i.e. their allocations are reused by
+    /// subsequent calls.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use jsonc_to_json::{JsonCPreprocessor, Stage};
+    ///
+    /// let mut preprocessor = JsonCPreprocessor::new()
+    ///     .stage(Stage::StripComments)
+    ///     .stage(Stage::StripTrailingCommas);
+    ///
+    /// for jsonc in ["[1, /* Comment */ 2,]", "{\"a\": 1,} // Comment"] {
+    ///     let json = preprocessor.process_buffered(jsonc)?;
+    ///     println!("{}", json);
+    /// }
+    /// # Ok::<(), jsonc_to_json::JsonCError>(())
+    /// ```
+    ///
+    /// [`process()`]: Self::process
+    pub fn process_buffered(&mut self, jsonc: &str) -> Result<&str, JsonCError> {
+        let mut bufs = mem::take(&mut self.bufs);
+        let res = self.process_into(jsonc, &mut bufs);
+        self.bufs = bufs;
+
+        let i = match res? {
+            Some(i) => i,
+            None => {
+                self.bufs[0].clear();
+                self.bufs[0].push_str(jsonc);
+                0
+            }
+        };
+        Ok(&self.bufs[i])
+    }
+
+    /// Applies all stages to `jsonc` in order, alternating between writing
+    /// into `bufs[0]` and `bufs[1]`. Returns the index of the buffer
+    /// containing the output, or `None` if no stage changed `jsonc`.
+    fn process_into(
+        &self,
+        jsonc: &str,
+        bufs: &mut [String; 2],
+    ) -> Result<Option<usize>, JsonCError> {
+        let mut current = None;
+        for (i, stage) in self.stages.iter().enumerate() {
+            let [first, second] = &mut *bufs;
+            let (input, out, next) = match current {
+                None => (jsonc, first, 0),
+                Some(0) => (first.as_str(), second, 1),
+                Some(_) => (second.as_str(), first, 0),
+            };
+
+            out.clear();
+            match stage.apply_into(input, out, None) {
+                Ok(true) => current = Some(next),
+                Ok(false) => {}
+                Err(err) => return Err(self.map_error(jsonc, i, err)),
+            }
+        }
+        Ok(current)
+    }
+
+    /// Maps the byte offset of `err` returned by the stage at index `i`
+    /// back into `jsonc`, by applying the previous stages again, while
+    /// keeping track of how they moved byte offsets.
+    #[cold]
+    fn map_error(&self, jsonc: &str, i: usize, err: JsonCError) -> JsonCError {
+        let mut maps = Vec::new();
+        let mut input = Cow::Borrowed(jsonc);
+        for stage in &self.stages[..i] {
+            let mut out = String::new();
+            let mut offsets = OffsetMap::default();
+            // The previous stages already succeeded once
+            if let Ok(true) = stage.apply_into(&input, &mut out, Some(&mut offsets)) {
+                input = Cow::Owned(out);
+                maps.push(offsets);
+            }
+        }
+
+        err.map_byte_offset(|offset| {
+            maps.iter()
+                .rev()
+                .fold(offset, |offset, offsets| offsets.to_input(offset))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::jsonc_to_json;
+
+    #[test]
+    fn test_stage_order() {
+        let jsonc = "[1, /* a */ 2, // b\r\n]";
+
+        let preprocessor = JsonCPreprocessor::new();
+        assert!(matches!(preprocessor.process(jsonc), Ok(Cow::Borrowed(_))));
+
+        let preprocessor = JsonCPreprocessor::new()
+            .stage(Stage::StripComments)
+            .stage(Stage::StripTrailingCommas);
+        assert_eq!(preprocessor.process(jsonc).unwrap(), jsonc_to_json(jsonc));
+
+        // Trailing commas are detected across comments, i.e. before they are removed
+        let preprocessor = JsonCPreprocessor::new()
+            .stage(Stage::StripTrailingCommas)
+            .stage(Stage::StripComments)
+            .stage(Stage::NormalizeCrlf);
+        assert_eq!(preprocessor.process(jsonc).unwrap(), "[1,  2 \n]");
+    }
+
+    #[test]
+    fn test_process_buffered() {
+        let mut preprocessor = JsonCPreprocessor::new()
+            .stage(Stage::ExpandEnvVars(UnknownVar::Error))
+            .stage(Stage::StripComments);
+
+        assert_eq!(preprocessor.process_buffered("[1, /**/ 2]"), Ok("[1,  2]"));
+        assert_eq!(preprocessor.process_buffered("[3]"), Ok("[3]"));
+
+        let jsonc = "[\"${JSONC_TO_JSON_UNSET}\"]";
+        assert_eq!(
+            preprocessor.process_buffered(jsonc),
+            Err(JsonCError::UnknownVariable { byte_offset: 2 })
+        );
+    }
+
+    #[test]
+    fn test_error_byte_offset() {
+        let jsonc = "/* comment */ [\"${JSONC_TO_JSON_UNSET}\"]";
+        let mut preprocessor = JsonCPreprocessor::new()
+            .stage(Stage::StripComments)
+            .stage(Stage::ExpandEnvVars(UnknownVar::Error));
+        let err = JsonCError::UnknownVariable { byte_offset: 16 };
+        assert_eq!(preprocessor.process(jsonc), Err(err.clone()));
+        assert_eq!(preprocessor.process_buffered(jsonc), Err(err));
+
+        let jsonc = "[\r\n\"${JSONC_TO_JSON_UNSET_A}\", /**/\r\n\"${JSONC_TO_JSON_UNSET_B}\"]";
+        let preprocessor = JsonCPreprocessor::new()
+            .stage(Stage::NormalizeCrlf)
+            .stage(Stage::StripComments)
+            .stage(Stage::ExpandEnvVars(UnknownVar::Empty))
+            .stage(Stage::ExpandEnvVars(UnknownVar::Error));
+        assert_eq!(preprocessor.process(jsonc).unwrap(), "[\n\"\", \n\"\"]");
+
+        let preprocessor = JsonCPreprocessor::new()
+            .stage(Stage::NormalizeCrlf)
+            .stage(Stage::StripComments)
+            .stage(Stage::ExpandEnvVars(UnknownVar::Preserve))
+            .stage(Stage::ExpandEnvVars(UnknownVar::Error));
+        assert_eq!(
+            preprocessor.process(jsonc),
+            Err(JsonCError::UnknownVariable { byte_offset: 4 })
+        );
+
+        let jsonc = "[1, /**/\r\n\"${JSONC_TO_JSON_UNSET}\"]";
+        let preprocessor = JsonCPreprocessor::new()
+            .stage(Stage::StripComments)
+            .stage(Stage::NormalizeCrlf)
+            .stage(Stage::ExpandEnvVars(UnknownVar::Error));
+        assert_eq!(
+            preprocessor.process(jsonc),
+            Err(JsonCError::UnknownVariable { byte_offset: 11 })
+        );
+    }
+}
@@ -0,0 +1,143 @@
+use std::io::{self, BufRead, Read};
+
+use crate::JsonCToJsonIter;
+
+/// [`Read`] implementation which streams the converted [JSON] from
+/// a [`JsonCToJsonIter`], i.e. without first building the whole output.
+///
+/// The string slices yielded by the iterator are read directly, i.e.
+/// the reader does not allocate any intermediate buffer. If a slice
+/// does not fit into the buffer passed to [`read()`], then the rest
+/// of the slice is returned by subsequent reads.
+///
+/// See also [`JsonCToJsonBufReader`] for converting [JSON with Comments]
+/// read from another reader.
+///
+/// # Example
+///
+/// ```rust
+/// use std::io::Read;
+/// use jsonc_to_json::JsonCToJsonReader;
+///
+/// let jsonc = "{\"arr\": [1, 2,/* Comment */ 3, 4,,]}// Line Comment";
+///
+/// let mut reader = JsonCToJsonReader::new(jsonc);
+///
+/// let mut json = String::new();
+/// reader.read_to_string(&mut json)?;
+/// assert_eq!(json, "{\"arr\": [1, 2, 3, 4]}");
+/// # Ok::<(), std::io::Error>(())
+/// ```
+///
+/// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+/// [JSON]: https://www.json.org/json-en.html
+/// [`read()`]: Read::read
+/// [`JsonCToJsonBufReader`]: crate::JsonCToJsonBufReader
+#[derive(Clone, Debug)]
+pub struct JsonCToJsonReader<'jsonc> {
+    iter: JsonCToJsonIter<'jsonc>,
+    /// The unread remainder of the last string slice yielded by `iter`,
+    /// which is bytes as reads are not required to end at a char boundary.
+    current: &'jsonc [u8],
+}
+
+impl<'jsonc> JsonCToJsonReader<'jsonc> {
+    /// Creates a new `JsonCToJsonReader`, which reads the converted `jsonc`.
+    #[must_use]
+    #[inline]
+    pub fn new(jsonc: &'jsonc str) -> Self {
+        Self::from(JsonCToJsonIter::new(jsonc))
+    }
+}
+
+impl<'jsonc> JsonCToJsonIter<'jsonc> {
+    /// Turns the iterator into a [`JsonCToJsonReader`], which reads
+    /// the remaining string slices as bytes.
+    ///
+    /// [`Read`] is not implemented by [`JsonCToJsonIter`] itself, as a
+    /// [`read()`] can end in the middle of a multi-byte [`char`], after
+    /// which the remaining output can no longer be yielded as [`str`].
+    /// The reader does not allocate, i.e. it only adds a byte slice to
+    /// the iterator.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::io::Read;
+    /// use jsonc_to_json::jsonc_to_json_iter;
+    ///
+    /// let mut iter = jsonc_to_json_iter("[1, /* Comment */ 2,] [3]");
+    /// assert_eq!(iter.next(), Some("[1, "));
+    ///
+    /// let mut reader = iter.into_reader();
+    /// let mut buf = [0; 4];
+    /// reader.read_exact(&mut buf)?;
+    /// assert_eq!(&buf, b" 2] ");
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    ///
+    /// [`read()`]: Read::read
+    #[inline]
+    pub fn into_reader(self) -> JsonCToJsonReader<'jsonc> {
+        JsonCToJsonReader::from(self)
+    }
+}
+
+impl<'jsonc> From<JsonCToJsonIter<'jsonc>> for JsonCToJsonReader<'jsonc> {
+    #[inline]
+    fn from(iter: JsonCToJsonIter<'jsonc>) -> Self {
+        Self { iter, current: &[] }
+    }
+}
+
+impl Read for JsonCToJsonReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let available = self.fill_buf()?;
+        let n = available.len().min(buf.len());
+        buf[..n].copy_from_slice(&available[..n]);
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl BufRead for JsonCToJsonReader<'_> {
+    #[inline]
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.current.is_empty() {
+            self.current = self.iter.next().map_or(&[], str::as_bytes);
+        }
+        Ok(self.current)
+    }
+
+    #[inline]
+    fn consume(&mut self, amt: usize) {
+        self.current = &self.current[amt.min(self.current.len())..];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::jsonc_to_json;
+
+    #[test]
+    fn test_read_small_buffer() {
+        let jsonc = "{\"æøå\": [1, /* ø */ \"日本語\",], // Comment\n}";
+        let expected = jsonc_to_json(jsonc);
+
+        for size in 1..8 {
+            let mut reader = JsonCToJsonReader::new(jsonc);
+            let mut json = Vec::new();
+            let mut buf = vec![0; size];
+            loop {
+                let n = reader.read(&mut buf).unwrap();
+                if n == 0 {
+                    break;
+                }
+                json.extend_from_slice(&buf[..n]);
+            }
+            assert_eq!(String::from_utf8(json).unwrap(), expected);
+        }
+    }
+}
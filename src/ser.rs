@@ -0,0 +1,366 @@
+use std::collections::HashMap;
+use std::io;
+
+use serde::Serialize;
+use serde_json::ser::{CharEscape, Formatter, PrettyFormatter};
+
+use crate::header::comment_lines;
+
+/// [`serde_json::Serializer`] producing [JSON with Comments], i.e.
+/// pretty printed [JSON] with comments inserted before object keys,
+/// see [`JsonCFormatter`].
+///
+/// _Requires the `serde_json` feature._
+///
+/// # Example
+///
+/// ```rust
+/// use serde::Serialize;
+/// use jsonc_to_json::{JsonCFormatter, JsonCSerializer};
+///
+/// #[derive(Serialize)]
+/// struct Config {
+///     tab_size: u32,
+///     theme: &'static str,
+/// }
+///
+/// let formatter = JsonCFormatter::new().comment("tab_size", "Size of a tab in spaces");
+///
+/// let mut jsonc = Vec::new();
+/// let mut ser = JsonCSerializer::with_formatter(&mut jsonc, formatter);
+/// Config { tab_size: 4, theme: "dark" }.serialize(&mut ser)?;
+///
+/// assert_eq!(
+///     String::from_utf8(jsonc).unwrap(),
+///     "{\n  // Size of a tab in spaces\n  \"tab_size\": 4,\n  \"theme\": \"dark\"\n}"
+/// );
+/// # Ok::<(), serde_json::Error>(())
+/// ```
+///
+/// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+/// [JSON]: https://www.json.org/json-en.html
+/// [`serde_json::Serializer`]: https://docs.rs/serde_json/latest/serde_json/struct.Serializer.html
+pub type JsonCSerializer<W> = serde_json::Serializer<W, JsonCFormatter>;
+
+/// [`Formatter`] which pretty prints [JSON] like [`PrettyFormatter`],
+/// and inserts a line comment before each object key, which has a
+/// comment specified using [`comment()`].
+///
+/// Keys are matched as they are written in the output, i.e. keys
+/// containing characters, which are escaped, must be specified escaped.
+/// A comment is inserted before every occurrence of the key, at any depth.
+///
+/// See [`JsonCSerializer`] and [`to_jsonc_string()`].
+///
+/// _Requires the `serde_json` feature._
+///
+/// [JSON]: https://www.json.org/json-en.html
+/// [`Formatter`]: https://docs.rs/serde_json/latest/serde_json/ser/trait.Formatter.html
+/// [`PrettyFormatter`]: https://docs.rs/serde_json/latest/serde_json/ser/struct.PrettyFormatter.html
+/// [`comment()`]: Self::comment
+#[derive(Clone, Debug)]
+pub struct JsonCFormatter {
+    inner: PrettyFormatter<'static>,
+    indent: &'static [u8],
+    depth: usize,
+    comments: HashMap<String, String>,
+    /// When writing an object key, then the key is buffered, such
+    /// that the comment for it can be written before it.
+    key: Option<Vec<u8>>,
+}
+
+impl JsonCFormatter {
+    /// Creates a new `JsonCFormatter`, which indents using two spaces.
+    #[must_use]
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_indent(b"  ")
+    }
+
+    /// Creates a new `JsonCFormatter`, which indents using `indent`.
+    #[must_use]
+    #[inline]
+    pub fn with_indent(indent: &'static [u8]) -> Self {
+        Self {
+            inner: PrettyFormatter::with_indent(indent),
+            indent,
+            depth: 0,
+            comments: HashMap::new(),
+            key: None,
+        }
+    }
+
+    /// Inserts `comment` before the object key `key`. Each line of
+    /// `comment` is written as a separate line comment.
+    #[must_use]
+    pub fn comment(mut self, key: impl Into<String>, comment: impl Into<String>) -> Self {
+        self.comments.insert(key.into(), comment.into());
+        self
+    }
+
+    fn write_indent<W>(&self, writer: &mut W) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        for _ in 0..self.depth {
+            writer.write_all(self.indent)?;
+        }
+        Ok(())
+    }
+}
+
+impl Default for JsonCFormatter {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Forwards to the inner [`PrettyFormatter`], writing to the
+/// buffered key instead of `writer` while writing an object key.
+macro_rules! forward_to_key {
+    ($($name:ident($($arg:ident: $ty:ty),*);)*) => {
+        $(
+            #[inline]
+            fn $name<W>(&mut self, writer: &mut W, $($arg: $ty),*) -> io::Result<()>
+            where
+                W: ?Sized + io::Write,
+            {
+                match &mut self.key {
+                    Some(key) => self.inner.$name(key, $($arg),*),
+                    None => self.inner.$name(writer, $($arg),*),
+                }
+            }
+        )*
+    };
+}
+
+impl Formatter for JsonCFormatter {
+    forward_to_key! {
+        write_null();
+        write_bool(value: bool);
+        write_i8(value: i8);
+        write_i16(value: i16);
+        write_i32(value: i32);
+        write_i64(value: i64);
+        write_i128(value: i128);
+        write_u8(value: u8);
+        write_u16(value: u16);
+        write_u32(value: u32);
+        write_u64(value: u64);
+        write_u128(value: u128);
+        write_f32(value: f32);
+        write_f64(value: f64);
+        write_number_str(value: &str);
+        begin_string();
+        end_string();
+        write_string_fragment(fragment: &str);
+        write_char_escape(char_escape: CharEscape);
+        write_byte_array(value: &[u8]);
+        write_raw_fragment(fragment: &str);
+    }
+
+    #[inline]
+    fn begin_array<W>(&mut self, writer: &mut W) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        self.depth += 1;
+        self.inner.begin_array(writer)
+    }
+
+    #[inline]
+    fn end_array<W>(&mut self, writer: &mut W) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        self.depth -= 1;
+        self.inner.end_array(writer)
+    }
+
+    #[inline]
+    fn begin_array_value<W>(&mut self, writer: &mut W, first: bool) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        self.inner.begin_array_value(writer, first)
+    }
+
+    #[inline]
+    fn end_array_value<W>(&mut self, writer: &mut W) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        self.inner.end_array_value(writer)
+    }
+
+    #[inline]
+    fn begin_object<W>(&mut self, writer: &mut W) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        self.depth += 1;
+        self.inner.begin_object(writer)
+    }
+
+    #[inline]
+    fn end_object<W>(&mut self, writer: &mut W) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        self.depth -= 1;
+        self.inner.end_object(writer)
+    }
+
+    #[inline]
+    fn begin_object_key<W>(&mut self, writer: &mut W, first: bool) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        self.inner.begin_object_key(writer, first)?;
+        self.key = Some(Vec::new());
+        Ok(())
+    }
+
+    fn end_object_key<W>(&mut self, writer: &mut W) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        let key = self.key.take().unwrap_or_default();
+
+        let name = key
+            .strip_prefix(b"\"")
+            .and_then(|key| key.strip_suffix(b"\""))
+            .and_then(|key| std::str::from_utf8(key).ok());
+        if let Some(comment) = name.and_then(|name| self.comments.get(name)) {
+            // The indentation before the key was already
+            // written by `begin_object_key()`
+            for line in comment_lines(comment) {
+                writer.write_all(b"//")?;
+                if !line.is_empty() {
+                    writer.write_all(b" ")?;
+                    writer.write_all(line.as_bytes())?;
+                }
+                writer.write_all(b"\n")?;
+                self.write_indent(writer)?;
+            }
+        }
+
+        writer.write_all(&key)?;
+        self.inner.end_object_key(writer)
+    }
+
+    #[inline]
+    fn begin_object_value<W>(&mut self, writer: &mut W) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        self.inner.begin_object_value(writer)
+    }
+
+    #[inline]
+    fn end_object_value<W>(&mut self, writer: &mut W) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        self.inner.end_object_value(writer)
+    }
+}
+
+/// Serializes `value` into [JSON with Comments] using `formatter`,
+/// see [`JsonCFormatter`].
+///
+/// _Requires the `serde_json` feature._
+///
+/// # Example
+///
+/// ```rust
+/// use std::collections::BTreeMap;
+/// use jsonc_to_json::{to_jsonc_string, JsonCFormatter};
+///
+/// let value = BTreeMap::from([("a", [1, 2]), ("b", [3, 4])]);
+///
+/// let formatter = JsonCFormatter::new().comment("b", "Line 1\nLine 2");
+/// let jsonc = to_jsonc_string(&value, formatter)?;
+/// assert_eq!(
+///     jsonc,
+///     r#"{
+///   "a": [
+///     1,
+///     2
+///   ],
+///   // Line 1
+///   // Line 2
+///   "b": [
+///     3,
+///     4
+///   ]
+/// }"#
+/// );
+/// # Ok::<(), serde_json::Error>(())
+/// ```
+///
+/// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+pub fn to_jsonc_string<T>(value: &T, formatter: JsonCFormatter) -> serde_json::Result<String>
+where
+    T: ?Sized + Serialize,
+{
+    let mut jsonc = Vec::new();
+    value.serialize(&mut JsonCSerializer::with_formatter(&mut jsonc, formatter))?;
+    // The formatter only writes valid UTF-8
+    Ok(String::from_utf8(jsonc).expect("invalid UTF-8"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    use crate::jsonc_to_value;
+
+    #[test]
+    fn test_to_jsonc_string_roundtrip() {
+        let value = json!({
+            "a": {"b": [1, {"c": null, "d\"": "e"}], "": {}},
+            "c": [],
+            "f": 1.5,
+        });
+        let formatter = JsonCFormatter::with_indent(b"\t")
+            .comment("a", "A")
+            .comment("c", "C\n\nC")
+            .comment("d\\\"", "D")
+            .comment("", "Empty");
+
+        let jsonc = to_jsonc_string(&value, formatter).unwrap();
+        assert_eq!(jsonc.matches("// A\n").count(), 1, "{}", jsonc);
+        assert_eq!(jsonc.matches("// C\n").count(), 4, "{}", jsonc);
+        assert_eq!(jsonc.matches("// D\n").count(), 1, "{}", jsonc);
+        assert_eq!(jsonc.matches("// Empty\n").count(), 1, "{}", jsonc);
+        assert_eq!(jsonc_to_value(&jsonc).unwrap(), value);
+    }
+
+    #[test]
+    fn test_non_string_keys() {
+        use std::collections::BTreeMap;
+
+        let value = BTreeMap::from([(1, true), (2, false)]);
+        let formatter = JsonCFormatter::new().comment("2", "Two");
+
+        let jsonc = to_jsonc_string(&value, formatter).unwrap();
+        assert_eq!(jsonc, "{\n  \"1\": true,\n  // Two\n  \"2\": false\n}");
+    }
+
+    #[test]
+    fn test_comment_lone_carriage_return() {
+        let value = json!({"a": [1, 2]});
+        let formatter = JsonCFormatter::new().comment("a", "A\rB\r\nC");
+
+        let jsonc = to_jsonc_string(&value, formatter).unwrap();
+        assert_eq!(
+            jsonc,
+            "{\n  // A\n  // B\n  // C\n  \"a\": [\n    1,\n    2\n  ]\n}"
+        );
+        assert_eq!(jsonc_to_value(&jsonc).unwrap(), value);
+    }
+}
@@ -0,0 +1,45 @@
+use smol_str::SmolStr;
+
+use crate::JsonCToJsonIter;
+
+/// Same as [`jsonc_to_json()`], but returns a [`SmolStr`].
+///
+/// If the output [JSON] is at most 23 bytes, then it is stored
+/// inline, i.e. without allocating.
+///
+/// _Requires the `smol_str` feature._
+///
+/// # Example
+///
+/// ```rust
+/// use jsonc_to_json::jsonc_to_json_smol;
+///
+/// let jsonc = "[1, 2, /* Comment */ 3,]";
+/// let json = jsonc_to_json_smol(jsonc);
+/// assert_eq!(json, "[1, 2,  3]");
+/// assert!(!json.is_heap_allocated());
+/// ```
+///
+/// [JSON]: https://www.json.org/json-en.html
+/// [`jsonc_to_json()`]: crate::jsonc_to_json
+/// [`SmolStr`]: https://docs.rs/smol_str/latest/smol_str/struct.SmolStr.html
+#[must_use = "this returns the converted JSON; dropping it does nothing"]
+#[inline]
+pub fn jsonc_to_json_smol(jsonc: &str) -> SmolStr {
+    JsonCToJsonIter::new(jsonc).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::jsonc_to_json;
+
+    #[test]
+    fn test_smol_heap() {
+        let jsonc = "{\"arr\": [1, 2,/* Comment */ 3, 4,,], \"foo\": \"bar\"}// Line Comment";
+        let json = jsonc_to_json_smol(jsonc);
+        assert_eq!(json.as_str(), jsonc_to_json(jsonc));
+        assert!(json.is_heap_allocated());
+    }
+}
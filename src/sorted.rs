@@ -0,0 +1,92 @@
+use indexmap::IndexMap;
+use serde::{Serialize, Serializer};
+use serde_json::Value;
+
+use crate::{jsonc_to_value, JsonCValueError};
+
+/// Converts [JSON with Comments] into [JSON] with the keys of all
+/// objects sorted, and without any whitespace, e.g. for producing
+/// deterministic output for content-addressed caching.
+///
+/// The [JSON with Comments] is converted and parsed using
+/// [`jsonc_to_value()`], after which the keys of all nested objects
+/// are sorted by their code points.
+///
+/// **Note:** This is a simpler subset of [RFC 8785] JSON Canonicalization
+/// and does not implement it fully. Notably, numbers are serialized as
+/// is by [`serde_json`] and are not normalized, and keys are not sorted
+/// by their UTF-16 code units, which differs for some non-ASCII keys.
+///
+/// _Requires the `indexmap` feature._
+///
+/// # Example
+///
+/// ```rust
+/// use jsonc_to_json::jsonc_to_json_sorted;
+///
+/// let jsonc = "{\"b\": [{\"y\": 1, \"x\": 2,}], /* Comment */ \"a\": null}";
+///
+/// let json = jsonc_to_json_sorted(jsonc)?;
+/// assert_eq!(json, "{\"a\":null,\"b\":[{\"x\":2,\"y\":1}]}");
+/// # Ok::<(), jsonc_to_json::JsonCValueError>(())
+/// ```
+///
+/// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+/// [JSON]: https://www.json.org/json-en.html
+/// [RFC 8785]: https://www.rfc-editor.org/rfc/rfc8785
+/// [`serde_json`]: https://docs.rs/serde_json
+pub fn jsonc_to_json_sorted(jsonc: &str) -> Result<String, JsonCValueError> {
+    let value = jsonc_to_value(jsonc)?;
+    Ok(serde_json::to_string(&Sorted(&value))?)
+}
+
+/// Serializes the wrapped value with the keys of all objects sorted,
+/// regardless of whether `serde_json` preserves the order of keys.
+struct Sorted<'a>(&'a Value);
+
+impl Serialize for Sorted<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self.0 {
+            Value::Array(values) => serializer.collect_seq(values.iter().map(Sorted)),
+            Value::Object(map) => {
+                let mut entries = map
+                    .iter()
+                    .map(|(key, value)| (key.as_str(), Sorted(value)))
+                    .collect::<IndexMap<_, _>>();
+                entries.sort_unstable_keys();
+                serializer.collect_map(entries)
+            }
+            value => value.serialize(serializer),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sorted_is_deterministic() {
+        let a = "{\"z\": {\"b\": 1, \"a\": [3, {\"d\": 4, \"c\": 5}]}, \"y\": \"æ\"}";
+        let b =
+            "{\"y\": \"æ\", // Comment\n \"z\": {\"a\": [3, {\"c\": 5, \"d\": 4,},], \"b\": 1}}";
+
+        let json = jsonc_to_json_sorted(a).unwrap();
+        assert_eq!(
+            json,
+            "{\"y\":\"æ\",\"z\":{\"a\":[3,{\"c\":5,\"d\":4}],\"b\":1}}"
+        );
+        assert_eq!(jsonc_to_json_sorted(b).unwrap(), json);
+    }
+
+    #[test]
+    fn test_sorted_invalid() {
+        assert!(matches!(
+            jsonc_to_json_sorted("{\"a\": 1 \"b\": 2}"),
+            Err(JsonCValueError::JsonC(_))
+        ));
+    }
+}
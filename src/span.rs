@@ -0,0 +1,69 @@
+use std::ops::Range;
+
+/// Byte range in the input, i.e. the same as
+/// <code>[Range]<[usize]></code>, but [`Copy`] and, with the
+/// `serde` feature, serializable.
+///
+/// # Example
+///
+/// ```rust
+/// use jsonc_to_json::Span;
+///
+/// let jsonc = "[1, /* Comment */ 2]";
+///
+/// let span = Span::from(4..17);
+/// assert_eq!(&jsonc[span.range()], "/* Comment */");
+/// assert_eq!(span.len(), 13);
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Default, Debug)]
+pub struct Span {
+    /// Byte offset of the start, inclusive.
+    pub start: usize,
+    /// Byte offset of the end, exclusive.
+    pub end: usize,
+}
+
+impl Span {
+    /// Creates a new `Span` from `start` to `end`.
+    #[must_use]
+    #[inline]
+    pub const fn new(start: usize, end: usize) -> Self {
+        Self { start, end }
+    }
+
+    /// Returns the span as a <code>[Range]<[usize]></code>.
+    #[must_use]
+    #[inline]
+    pub const fn range(self) -> Range<usize> {
+        self.start..self.end
+    }
+
+    /// Returns the length of the span in bytes.
+    #[must_use]
+    #[inline]
+    pub const fn len(self) -> usize {
+        self.end.saturating_sub(self.start)
+    }
+
+    /// Returns `true` if the span is empty.
+    #[must_use]
+    #[inline]
+    pub const fn is_empty(self) -> bool {
+        self.start >= self.end
+    }
+}
+
+impl From<Range<usize>> for Span {
+    #[inline]
+    fn from(range: Range<usize>) -> Self {
+        Self::new(range.start, range.end)
+    }
+}
+
+impl From<Span> for Range<usize> {
+    #[inline]
+    fn from(span: Span) -> Self {
+        span.range()
+    }
+}
@@ -0,0 +1,119 @@
+use std::borrow::Cow;
+
+use crate::{collect_cow_with, JsonCToJsonIter, Removed};
+
+/// Statistics about a conversion, see [`jsonc_to_json_with_stats()`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Default, Debug)]
+pub struct ConversionStats {
+    /// Length of the input in bytes.
+    pub input_len: usize,
+    /// Length of the output in bytes.
+    pub output_len: usize,
+    /// Number of line comments removed.
+    pub line_comments: usize,
+    /// Number of block comments removed.
+    pub block_comments: usize,
+    /// Number of trailing commas removed.
+    pub trailing_commas: usize,
+}
+
+impl ConversionStats {
+    /// Returns the number of bytes removed, i.e.
+    /// `input_len - output_len`.
+    #[must_use]
+    #[inline]
+    pub fn bytes_removed(&self) -> usize {
+        self.input_len - self.output_len
+    }
+}
+
+/// Same as [`jsonc_to_json()`], but additionally returns [`ConversionStats`]
+/// about the conversion.
+///
+/// The statistics are collected while converting, i.e. without
+/// a separate pass over `jsonc`.
+///
+/// # Example
+///
+/// ```rust
+/// use jsonc_to_json::{jsonc_to_json_with_stats, ConversionStats};
+///
+/// let jsonc = "{\"arr\": [1, 2,/* Comment */ 3, 4,,]}// Line Comment";
+///
+/// let (json, stats) = jsonc_to_json_with_stats(jsonc);
+/// assert_eq!(json, "{\"arr\": [1, 2, 3, 4]}");
+/// assert_eq!(
+///     stats,
+///     ConversionStats {
+///         input_len: 51,
+///         output_len: 21,
+///         line_comments: 1,
+///         block_comments: 1,
+///         trailing_commas: 2,
+///     }
+/// );
+/// ```
+///
+/// [`jsonc_to_json()`]: crate::jsonc_to_json
+#[must_use = "this returns the converted JSON; dropping it does nothing"]
+pub fn jsonc_to_json_with_stats(jsonc: &str) -> (Cow<'_, str>, ConversionStats) {
+    let mut stats = ConversionStats {
+        input_len: jsonc.len(),
+        ..ConversionStats::default()
+    };
+
+    let mut iter = JsonCToJsonIter::new(jsonc);
+    let mut on_removed = |removed, _range| match removed {
+        Removed::LineComment => stats.line_comments += 1,
+        Removed::BlockComment => stats.block_comments += 1,
+        Removed::TrailingComma { .. } => stats.trailing_commas += 1,
+    };
+    let json = collect_cow_with(jsonc.len(), || iter.next_with(&mut on_removed));
+
+    stats.output_len = json.len();
+
+    (json, stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::jsonc_to_json;
+
+    #[test]
+    fn test_stats_borrowed() {
+        let jsonc = r#"{"arr": [1, 2, 3, 4]}"#;
+        let (json, stats) = jsonc_to_json_with_stats(jsonc);
+        assert!(matches!(json, Cow::Borrowed(_)));
+        assert_eq!(stats.bytes_removed(), 0);
+        assert_eq!(
+            stats.line_comments + stats.block_comments + stats.trailing_commas,
+            0
+        );
+    }
+
+    #[test]
+    fn test_stats_output() {
+        let jsonc = "// A\n{\"a\": [1,,2,], /* B */ /* C */ \"b\": {,},} // D";
+        let (json, stats) = jsonc_to_json_with_stats(jsonc);
+        assert_eq!(json, jsonc_to_json(jsonc));
+        assert_eq!(stats.output_len, json.len());
+        assert_eq!(stats.line_comments, 2);
+        assert_eq!(stats.block_comments, 2);
+        assert_eq!(stats.trailing_commas, 4);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serialize_stats() {
+        let (_json, stats) = jsonc_to_json_with_stats("[1, 2,] // Comment");
+
+        let json = serde_json::to_string(&stats).unwrap();
+        assert_eq!(
+            json,
+            r#"{"input_len":18,"output_len":7,"line_comments":1,"block_comments":0,"trailing_commas":1}"#
+        );
+    }
+}
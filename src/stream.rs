@@ -0,0 +1,399 @@
+use std::io::{self, BufRead, Read};
+
+/// Incremental [JSON with Comments] to [JSON] converter, operating on
+/// chunks of bytes, such that comments, strings, and trailing commas
+/// can span across chunk boundaries.
+///
+/// The output is the same as [`jsonc_to_json()`], given the same input.
+///
+/// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+/// [JSON]: https://www.json.org/json-en.html
+/// [`jsonc_to_json()`]: crate::jsonc_to_json
+#[derive(Clone, Default, Debug)]
+pub(crate) struct StreamStripper {
+    state: StreamState,
+    /// When not empty, then it contains a comma followed by zero-to-many
+    /// whitespace bytes, which are held back until the next significant
+    /// byte is known, i.e. until it is known whether the comma is a
+    /// trailing comma.
+    pending: Vec<u8>,
+    /// Bytes of a non-ASCII character following `pending`, which are
+    /// held back until the whole character is decoded, i.e. until it
+    /// is known whether the character is whitespace.
+    utf8: Vec<u8>,
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Default, Debug)]
+enum StreamState {
+    #[default]
+    Normal,
+    /// Encountered `/`, which could be the start of a comment.
+    Slash,
+    String,
+    StringEscape,
+    LineComment,
+    BlockComment,
+    /// Encountered `*` inside a block comment, which could be the
+    /// start of `*/`.
+    BlockCommentStar,
+    /// Decoding a non-ASCII character following a comma.
+    Utf8,
+}
+
+impl StreamStripper {
+    #[inline]
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Converts the next `chunk` of input, appending the output to `out`.
+    pub(crate) fn feed(&mut self, chunk: &[u8], out: &mut Vec<u8>) {
+        for &b in chunk {
+            self.feed_byte(b, out);
+        }
+    }
+
+    /// Flushes any remaining state, appending the output to `out`.
+    ///
+    /// After calling `finish()` the stripper is reset, and can be
+    /// used for new input.
+    pub(crate) fn finish(&mut self, out: &mut Vec<u8>) {
+        match self.state {
+            StreamState::Slash => self.significant(b'/', out),
+            // Incomplete UTF-8 sequences are not whitespace
+            StreamState::Utf8 => self.end_utf8(false, out),
+            _ => {}
+        }
+
+        // A comma at the end of the input is a trailing comma
+        if let Some((_comma, space)) = self.pending.split_first() {
+            out.extend_from_slice(space);
+        }
+
+        self.pending.clear();
+        self.state = StreamState::Normal;
+    }
+
+    fn feed_byte(&mut self, b: u8, out: &mut Vec<u8>) {
+        match self.state {
+            StreamState::Normal => self.normal(b, out),
+            StreamState::Slash => match b {
+                b'/' => self.state = StreamState::LineComment,
+                b'*' => self.state = StreamState::BlockComment,
+                _ => {
+                    self.state = StreamState::Normal;
+                    self.significant(b'/', out);
+                    self.normal(b, out);
+                }
+            },
+            StreamState::String => {
+                out.push(b);
+                match b {
+                    b'"' => self.state = StreamState::Normal,
+                    b'\\' => self.state = StreamState::StringEscape,
+                    _ => {}
+                }
+            }
+            StreamState::StringEscape => {
+                out.push(b);
+                self.state = StreamState::String;
+            }
+            StreamState::LineComment => {
+                if matches!(b, b'\n' | b'\r') {
+                    self.state = StreamState::Normal;
+                    self.normal(b, out);
+                }
+            }
+            StreamState::BlockComment => {
+                if b == b'*' {
+                    self.state = StreamState::BlockCommentStar;
+                }
+            }
+            StreamState::BlockCommentStar => match b {
+                b'/' => self.state = StreamState::Normal,
+                b'*' => {}
+                _ => self.state = StreamState::BlockComment,
+            },
+            StreamState::Utf8 => {
+                if (b & 0xC0) == 0x80 {
+                    self.push_utf8(b, out);
+                } else {
+                    self.end_utf8(false, out);
+                    self.normal(b, out);
+                }
+            }
+        }
+    }
+
+    fn normal(&mut self, b: u8, out: &mut Vec<u8>) {
+        match b {
+            b'/' => self.state = StreamState::Slash,
+            // Same as the tokenizer, i.e. `char::is_whitespace()`
+            _ if b.is_ascii() && char::from(b).is_whitespace() => {
+                if self.pending.is_empty() {
+                    out.push(b);
+                } else {
+                    self.pending.push(b);
+                }
+            }
+            b'"' => {
+                self.significant(b, out);
+                self.state = StreamState::String;
+            }
+            _ if !b.is_ascii() && !self.pending.is_empty() => {
+                self.state = StreamState::Utf8;
+                self.push_utf8(b, out);
+            }
+            _ => self.significant(b, out),
+        }
+    }
+
+    fn push_utf8(&mut self, b: u8, out: &mut Vec<u8>) {
+        self.utf8.push(b);
+        match std::str::from_utf8(&self.utf8) {
+            Ok(c) => {
+                let is_whitespace = c.chars().all(char::is_whitespace);
+                self.end_utf8(is_whitespace, out);
+            }
+            // Incomplete character
+            Err(err) if err.error_len().is_none() => {}
+            Err(_) => self.end_utf8(false, out),
+        }
+    }
+
+    fn end_utf8(&mut self, is_whitespace: bool, out: &mut Vec<u8>) {
+        self.state = StreamState::Normal;
+        if is_whitespace {
+            self.pending.extend_from_slice(&self.utf8);
+        } else {
+            // The comma is followed by something other than `}` or `]`
+            if let Some((comma, space)) = self.pending.split_first() {
+                out.push(*comma);
+                out.extend_from_slice(space);
+                self.pending.clear();
+            }
+            out.extend_from_slice(&self.utf8);
+        }
+        self.utf8.clear();
+    }
+
+    fn significant(&mut self, b: u8, out: &mut Vec<u8>) {
+        if let Some((comma, space)) = self.pending.split_first() {
+            if !matches!(b, b',' | b'}' | b']') {
+                out.push(*comma);
+            }
+            out.extend_from_slice(space);
+            self.pending.clear();
+        }
+
+        if b == b',' {
+            self.pending.push(b);
+        } else {
+            out.push(b);
+        }
+    }
+}
+
+/// Streaming [`BufRead`] adapter, which converts [JSON with Comments]
+/// read from `R` into [JSON] on the fly.
+///
+/// Comments, strings, and trailing commas are allowed to span across
+/// the buffer refills of `R`, i.e. a block comment whose `*/` is only
+/// returned by a later call to [`fill_buf()`] is handled correctly.
+///
+/// **Warning:** The conversion is infallible and does not validate the
+/// input. See [`jsonc_to_json()`] for more information.
+///
+/// # Example
+///
+/// ```rust
+/// use std::io::BufRead;
+/// use jsonc_to_json::JsonCToJsonBufReader;
+///
+/// let jsonc = "[1, 2, /* Comment */ 3,] // Line Comment\n{\"foo\": \"bar\",}\n";
+///
+/// let reader = JsonCToJsonBufReader::new(jsonc.as_bytes());
+/// let lines = reader.lines().collect::<Result<Vec<_>, _>>()?;
+/// assert_eq!(lines, ["[1, 2,  3] ", "{\"foo\": \"bar\"}"]);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+///
+/// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+/// [JSON]: https://www.json.org/json-en.html
+/// [`fill_buf()`]: BufRead::fill_buf
+/// [`jsonc_to_json()`]: crate::jsonc_to_json
+#[derive(Debug)]
+pub struct JsonCToJsonBufReader<R> {
+    inner: R,
+    stripper: StreamStripper,
+    buf: Vec<u8>,
+    pos: usize,
+    done: bool,
+}
+
+impl<R: BufRead> JsonCToJsonBufReader<R> {
+    /// Creates a new `JsonCToJsonBufReader`, which reads
+    /// [JSON with Comments] from `inner`.
+    ///
+    /// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+    #[inline]
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            stripper: StreamStripper::new(),
+            buf: Vec::new(),
+            pos: 0,
+            done: false,
+        }
+    }
+}
+
+impl<R> JsonCToJsonBufReader<R> {
+    /// Returns a reference to the underlying reader.
+    #[inline]
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    ///
+    /// **Note:** Reading directly from the underlying reader
+    /// will result in the read data not being converted.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Unwraps this `JsonCToJsonBufReader`, returning the underlying reader.
+    ///
+    /// **Note:** Any converted data, which has not been read yet is lost.
+    #[inline]
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: BufRead> Read for JsonCToJsonBufReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let available = self.fill_buf()?;
+        let n = available.len().min(buf.len());
+        buf[..n].copy_from_slice(&available[..n]);
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl<R: BufRead> BufRead for JsonCToJsonBufReader<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        // Converting a chunk can produce no output, e.g. if
+        // the whole chunk is inside a comment
+        while (self.pos == self.buf.len()) && !self.done {
+            self.buf.clear();
+            self.pos = 0;
+
+            let chunk = self.inner.fill_buf()?;
+            if chunk.is_empty() {
+                self.stripper.finish(&mut self.buf);
+                self.done = true;
+            } else {
+                self.stripper.feed(chunk, &mut self.buf);
+                let n = chunk.len();
+                self.inner.consume(n);
+            }
+        }
+
+        Ok(&self.buf[self.pos..])
+    }
+
+    #[inline]
+    fn consume(&mut self, amt: usize) {
+        self.pos = (self.pos + amt).min(self.buf.len());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::jsonc_to_json;
+
+    const INPUTS: &[&str] = &[
+        "",
+        r#"{"arr": [1, 2, 3, 4]}"#,
+        r#"{"arr": [1, 2,/* Comment */ 3, 4,,]}// Line Comment"#,
+        r#"{foo}/**/[1,2,3,,]"bar""#,
+        "[1, /* a * b ** / */ 2, // c\r\n 3 , /**/ ]",
+        "{\"a//b\": \"/*c*/\", \"d\\\"//\": [1 , , 2 ,\n]}",
+        "[1, 2 /",
+        "[1, 2 ,\n\t",
+        "/* unterminated",
+        "\"unterminated, ]",
+        "{\"æøå\": \"日本語\", /* ø */ \"x\": 1,}",
+        "{\"a\":1,\n//c1\n//c2\n}",
+        "[1,//c1\n//c2\n2,//c3\n//c4\n]",
+        "[[1],[2]]",
+        "[{\"a\":1},/*c*/{\"b\":2},]",
+        "[/** doc **/ 1, /* a **/ 2,/***/]",
+        "[1,\u{a0}]",
+        "[1,\u{b}]",
+        "{\"a\": 1,\u{2028}}",
+        "[1,\u{3000} \u{85}/* ø */\u{2029}]",
+        "[1,\u{a0}ø,\u{3000}日本語]",
+    ];
+
+    fn read_chunked(jsonc: &str, capacity: usize) -> String {
+        let inner = io::BufReader::with_capacity(capacity, jsonc.as_bytes());
+        let mut reader = JsonCToJsonBufReader::new(inner);
+        let mut json = String::new();
+        reader.read_to_string(&mut json).unwrap();
+        json
+    }
+
+    #[test]
+    fn test_stream_matches_jsonc_to_json() {
+        for jsonc in INPUTS {
+            let expected = jsonc_to_json(jsonc);
+            for capacity in 1..=jsonc.len().max(1) {
+                assert_eq!(
+                    read_chunked(jsonc, capacity),
+                    expected,
+                    "input {:?} with capacity {}",
+                    jsonc,
+                    capacity
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_stream_matches_jsonc_to_json_random() {
+        // Simple deterministic pseudo-random number generator
+        let mut seed = 0x9e37_79b9_u32;
+        let mut rand = |n: usize| {
+            seed ^= seed << 13;
+            seed ^= seed >> 17;
+            seed ^= seed << 5;
+            (seed as usize) % n
+        };
+
+        const TEXTS: &[&str] = &[
+            ",", ",", "]", "}", "[", "{", "1", "\"a\"", ":", " ", "\n", "\r", "\t", "\u{b}",
+            "\u{c}", "\u{85}", "\u{a0}", "\u{1680}", "\u{2000}", "\u{200b}", "\u{2028}",
+            "\u{2029}", "\u{3000}", "\u{feff}", "ø", "日", "/", "*", "/*", "*/", "//", "\\",
+        ];
+
+        for _ in 0..20_000 {
+            let jsonc = (0..rand(12))
+                .map(|_| TEXTS[rand(TEXTS.len())])
+                .collect::<String>();
+            let capacity = rand(jsonc.len().max(1)) + 1;
+            assert_eq!(
+                read_chunked(&jsonc, capacity),
+                jsonc_to_json(&jsonc),
+                "input {:?} with capacity {}",
+                jsonc,
+                capacity
+            );
+        }
+    }
+}
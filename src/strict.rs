@@ -0,0 +1,592 @@
+use std::borrow::Cow;
+use std::ops::ControlFlow;
+
+use any_lexer::{JsonCToken, TokenSpan};
+
+use crate::tokens::JsonCTokens;
+use crate::{jsonc_to_json, JsonCError};
+
+/// Same as [`jsonc_to_json()`], but returns [`JsonCError`] if `jsonc`
+/// is not valid [JSON with Comments].
+///
+/// In contrast to [`jsonc_to_json()`], the output is guaranteed
+/// to be valid [JSON].
+///
+/// Valid [JSON with Comments] is [JSON] with line comments, block
+/// comments, and at most a single trailing comma after the last
+/// element in arrays and objects, i.e. `[1,2,]` is valid, while
+/// `[1,2,,]` and `[,]` are invalid.
+///
+/// Any content after the root value, other than whitespace and comments,
+/// results in [`JsonCError::TrailingContent`], e.g. `{"a": 1} garbage`.
+///
+/// # Example
+///
+/// ```rust
+/// use jsonc_to_json::{jsonc_to_json_strict, JsonCError};
+///
+/// let jsonc = "{\"arr\": [1, 2,/* Comment */ 3, 4,]}// Line Comment";
+/// let json = jsonc_to_json_strict(jsonc);
+/// assert_eq!(json, Ok("{\"arr\": [1, 2, 3, 4]}".into()));
+///
+/// let jsonc = "{\"arr\": [1, 2 3]}";
+/// let err = jsonc_to_json_strict(jsonc);
+/// assert_eq!(err, Err(JsonCError::UnexpectedToken { byte_offset: 14 }));
+///
+/// let jsonc = "{\"a\": 1} // Comment\ngarbage";
+/// let err = jsonc_to_json_strict(jsonc);
+/// assert_eq!(err, Err(JsonCError::TrailingContent { byte_offset: 20 }));
+/// ```
+///
+/// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+/// [JSON]: https://www.json.org/json-en.html
+/// [`jsonc_to_json()`]: crate::jsonc_to_json
+pub fn jsonc_to_json_strict(jsonc: &str) -> Result<Cow<'_, str>, JsonCError> {
+    validate(JsonCTokens::new(jsonc))?;
+    Ok(jsonc_to_json(jsonc))
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+enum Container {
+    Object,
+    Array,
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+enum Expect {
+    /// The root value.
+    RootValue,
+    /// Value after `:`.
+    Value,
+    /// Value or `]` after `[`.
+    ArrayFirst,
+    /// Value or `]` after `,`, i.e. allowing a trailing comma.
+    ArrayNext,
+    /// Key or `}` after `{`.
+    ObjectFirst,
+    /// Key or `}` after `,`, i.e. allowing a trailing comma.
+    ObjectNext,
+    /// `:` after key.
+    Colon,
+    /// `,` or the end of the current array or object.
+    CommaOrEnd,
+    /// End of input after the root value.
+    End,
+}
+
+/// Validates that `tokens` is valid [JSON with Comments], returning
+/// the first encountered error.
+///
+/// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+pub(crate) fn validate(tokens: JsonCTokens<'_>) -> Result<(), JsonCError> {
+    let mut first = None;
+    let _ = validate_with(tokens, &mut Vec::new(), &mut |err| {
+        first = Some(err);
+        ControlFlow::Break(())
+    });
+
+    match first {
+        Some(err) => Err(err),
+        None => Ok(()),
+    }
+}
+
+/// Validates that `jsonc` is valid [JSON with Comments], returning
+/// all detectable errors, ordered by their position in the input.
+///
+/// Returns an empty [`Vec`] if `jsonc` is valid, i.e. if and only
+/// if [`jsonc_to_json_strict()`] succeeds. The first error is the
+/// same error returned by [`jsonc_to_json_strict()`].
+///
+/// After an error, validation continues on a best-effort basis, e.g.
+/// after a mismatched `}` the enclosing object is assumed to be closed.
+/// Content after a complete root value is reported only once.
+///
+/// # Example
+///
+/// ```rust
+/// use jsonc_to_json::{validate_jsonc, JsonCError};
+///
+/// let jsonc = "{\"a\": [1 2], \"b\": \"\\x\", /* Comment";
+///
+/// assert_eq!(
+///     validate_jsonc(jsonc),
+///     [
+///         JsonCError::UnexpectedToken { byte_offset: 9 },
+///         JsonCError::InvalidEscape { byte_offset: 19 },
+///         JsonCError::UnterminatedBlockComment { byte_offset: 24 },
+///         JsonCError::UnexpectedEof { byte_offset: 34 },
+///     ]
+/// );
+///
+/// assert_eq!(validate_jsonc("{\"a\": [1, 2,],}"), []);
+/// ```
+///
+/// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+#[must_use]
+pub fn validate_jsonc(jsonc: &str) -> Vec<JsonCError> {
+    JsonCValidator::new().validate(jsonc)
+}
+
+/// Validation context, for validating many inputs without
+/// converting them, see [`validate_jsonc()`].
+///
+/// Only the stack used to track nested arrays and objects is retained
+/// between calls to [`validate()`], which avoids reallocating it for
+/// each input. Every call still tokenizes the whole input, and the
+/// returned `Vec` is allocated if any errors are found.
+///
+/// # Example
+///
+/// ```rust
+/// use jsonc_to_json::{JsonCError, JsonCValidator};
+///
+/// let mut validator = JsonCValidator::new();
+///
+/// assert_eq!(validator.validate("{\"a\": [1, 2,],} // Comment"), []);
+/// assert_eq!(
+///     validator.validate("[1 2]"),
+///     [JsonCError::UnexpectedToken { byte_offset: 3 }]
+/// );
+/// ```
+///
+/// [`validate()`]: Self::validate
+#[derive(Clone, Default, Debug)]
+pub struct JsonCValidator {
+    stack: Vec<Container>,
+}
+
+impl JsonCValidator {
+    /// Creates a new `JsonCValidator`.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Validates that `jsonc` is valid [JSON with Comments], returning
+    /// all detectable errors.
+    ///
+    /// See [`validate_jsonc()`] for more information.
+    ///
+    /// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+    #[must_use]
+    pub fn validate(&mut self, jsonc: &str) -> Vec<JsonCError> {
+        let mut errors = Vec::new();
+        let _ = validate_with(JsonCTokens::new(jsonc), &mut self.stack, &mut |err| {
+            errors.push(err);
+            ControlFlow::Continue(())
+        });
+        errors
+    }
+}
+
+/// Validates `tokens`, calling `report` for each error, until
+/// `report` returns [`ControlFlow::Break`].
+///
+/// `stack` is cleared before use, and only passed in to allow
+/// reusing its allocation.
+fn validate_with<F>(
+    tokens: JsonCTokens<'_>,
+    stack: &mut Vec<Container>,
+    report: &mut F,
+) -> ControlFlow<()>
+where
+    F: FnMut(JsonCError) -> ControlFlow<()>,
+{
+    let len = tokens.text().len();
+
+    stack.clear();
+    let mut expect = Expect::RootValue;
+    let mut reported_trailing = false;
+
+    for (tok, span) in tokens {
+        let s = span.as_str();
+        let unexpected = || JsonCError::UnexpectedToken {
+            byte_offset: span.start(),
+        };
+
+        match tok {
+            JsonCToken::Space | JsonCToken::LineComment => continue,
+            JsonCToken::BlockComment => {
+                validate_block_comment(&span, report)?;
+                continue;
+            }
+            _ => {}
+        }
+
+        if expect == Expect::End {
+            if !reported_trailing {
+                report(JsonCError::TrailingContent {
+                    byte_offset: span.start(),
+                })?;
+            }
+            reported_trailing = true;
+            continue;
+        }
+
+        // Whether an error has already been reported for the token
+        let mut reported = false;
+        match tok {
+            JsonCToken::String => validate_string(&span, report)?,
+            JsonCToken::Number if !is_valid_number(s) => {
+                report(unexpected())?;
+                reported = true;
+            }
+            JsonCToken::Unknown => {
+                report(unexpected())?;
+                reported = true;
+            }
+            _ => {}
+        }
+
+        // Invalid numbers and unknown tokens are treated
+        // as values, after having been reported
+        let is_scalar = matches!(
+            tok,
+            JsonCToken::String
+                | JsonCToken::Number
+                | JsonCToken::Null
+                | JsonCToken::True
+                | JsonCToken::False
+                | JsonCToken::Unknown
+        );
+
+        let next = match (expect, s) {
+            (Expect::ArrayFirst | Expect::ArrayNext, "]") => {
+                stack.pop();
+                Some(after_value(stack))
+            }
+            (Expect::RootValue | Expect::Value | Expect::ArrayFirst | Expect::ArrayNext, _) => {
+                match s {
+                    _ if is_scalar => Some(after_value(stack)),
+                    "{" => {
+                        stack.push(Container::Object);
+                        Some(Expect::ObjectFirst)
+                    }
+                    "[" => {
+                        stack.push(Container::Array);
+                        Some(Expect::ArrayFirst)
+                    }
+                    _ => None,
+                }
+            }
+            (Expect::ObjectFirst | Expect::ObjectNext, "}") => {
+                stack.pop();
+                Some(after_value(stack))
+            }
+            (Expect::ObjectFirst | Expect::ObjectNext, _) if tok == JsonCToken::String => {
+                Some(Expect::Colon)
+            }
+            (Expect::Colon, ":") => Some(Expect::Value),
+            (Expect::CommaOrEnd, ",") => match stack.last() {
+                Some(Container::Object) => Some(Expect::ObjectNext),
+                Some(Container::Array) => Some(Expect::ArrayNext),
+                None => unreachable!(),
+            },
+            (Expect::CommaOrEnd, "}") if stack.last() == Some(&Container::Object) => {
+                stack.pop();
+                Some(after_value(stack))
+            }
+            (Expect::CommaOrEnd, "]") if stack.last() == Some(&Container::Array) => {
+                stack.pop();
+                Some(after_value(stack))
+            }
+            _ => None,
+        };
+
+        expect = match next {
+            Some(next) => next,
+            None => {
+                if !reported {
+                    report(unexpected())?;
+                }
+                recover(expect, s, is_scalar, stack)
+            }
+        };
+    }
+
+    if expect != Expect::End {
+        report(JsonCError::UnexpectedEof { byte_offset: len })?;
+    }
+
+    ControlFlow::Continue(())
+}
+
+#[inline]
+fn after_value(stack: &[Container]) -> Expect {
+    if stack.is_empty() {
+        Expect::End
+    } else {
+        Expect::CommaOrEnd
+    }
+}
+
+/// Returns what to expect after the unexpected token `s`,
+/// such that validation can continue.
+fn recover(expect: Expect, s: &str, is_scalar: bool, stack: &mut Vec<Container>) -> Expect {
+    match s {
+        "{" => {
+            stack.push(Container::Object);
+            Expect::ObjectFirst
+        }
+        "[" => {
+            stack.push(Container::Array);
+            Expect::ArrayFirst
+        }
+        // Mismatched closing bracket, which is assumed to
+        // close the nearest matching container
+        "}" | "]" => {
+            let container = if s == "}" {
+                Container::Object
+            } else {
+                Container::Array
+            };
+            match stack.iter().rposition(|&c| c == container) {
+                Some(i) => {
+                    stack.truncate(i);
+                    after_value(stack)
+                }
+                None => expect,
+            }
+        }
+        ":" => Expect::Value,
+        "," => match stack.last() {
+            Some(Container::Object) => Expect::ObjectNext,
+            Some(Container::Array) => Expect::ArrayNext,
+            None => expect,
+        },
+        _ if is_scalar => after_value(stack),
+        _ => expect,
+    }
+}
+
+fn validate_block_comment<F>(span: &TokenSpan<'_>, report: &mut F) -> ControlFlow<()>
+where
+    F: FnMut(JsonCError) -> ControlFlow<()>,
+{
+    let s = span.as_str();
+    if (s.len() < 4) || !s.ends_with("*/") {
+        report(JsonCError::UnterminatedBlockComment {
+            byte_offset: span.start(),
+        })?;
+    }
+    ControlFlow::Continue(())
+}
+
+fn validate_string<F>(span: &TokenSpan<'_>, report: &mut F) -> ControlFlow<()>
+where
+    F: FnMut(JsonCError) -> ControlFlow<()>,
+{
+    let mut chars = span.as_str().char_indices().skip(1);
+    while let Some((i, c)) = chars.next() {
+        let byte_offset = span.start() + i;
+        match c {
+            '"' => return ControlFlow::Continue(()),
+            '\\' => match chars.next() {
+                Some((_, '"' | '\\' | '/' | 'b' | 'f' | 'n' | 'r' | 't')) => {}
+                Some((_, 'u')) => {
+                    for _ in 0..4 {
+                        match chars.clone().next() {
+                            Some((_, c)) if c.is_ascii_hexdigit() => {
+                                chars.next();
+                            }
+                            Some(_) => {
+                                report(JsonCError::InvalidEscape { byte_offset })?;
+                                break;
+                            }
+                            None => break,
+                        }
+                    }
+                }
+                Some(_) => report(JsonCError::InvalidEscape { byte_offset })?,
+                None => break,
+            },
+            _ if c < ' ' => report(JsonCError::UnescapedControlCharacter { byte_offset })?,
+            _ => {}
+        }
+    }
+
+    report(JsonCError::UnterminatedString {
+        byte_offset: span.start(),
+    })
+}
+
+/// Returns `true` if `s` is a valid [JSON] number, i.e.
+/// `-?(0|[1-9][0-9]*)(\.[0-9]+)?([eE][+-]?[0-9]+)?`.
+///
+/// [JSON]: https://www.json.org/json-en.html
+fn is_valid_number(s: &str) -> bool {
+    fn digits(s: &[u8]) -> usize {
+        s.iter().take_while(|b| b.is_ascii_digit()).count()
+    }
+
+    let mut s = s.as_bytes();
+    if let [b'-', rest @ ..] = s {
+        s = rest;
+    }
+
+    match s {
+        [b'0', rest @ ..] => s = rest,
+        [b'1'..=b'9', ..] => s = &s[digits(s)..],
+        _ => return false,
+    }
+
+    if let [b'.', rest @ ..] = s {
+        let n = digits(rest);
+        if n == 0 {
+            return false;
+        }
+        s = &rest[n..];
+    }
+
+    if let [b'e' | b'E', rest @ ..] = s {
+        let rest = match rest {
+            [b'+' | b'-', rest @ ..] => rest,
+            _ => rest,
+        };
+        let n = digits(rest);
+        if n == 0 {
+            return false;
+        }
+        s = &rest[n..];
+    }
+
+    s.is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validate_str(jsonc: &str) -> Result<(), JsonCError> {
+        validate(JsonCTokens::new(jsonc))
+    }
+
+    #[test]
+    fn test_valid() {
+        let valid = [
+            "null",
+            " true // Comment",
+            "/* Comment */ false",
+            "[]",
+            "{}",
+            "[1, 2.5, -3e+10, 0, -0.0e0,]",
+            "{\"a\": {\"b\": [true, false, null],}, \"c\": \"\\u00e6\\n\",}",
+            "{\n  // Comment\n  \"a\": 1, /* Comment */\n}",
+        ];
+        for jsonc in valid {
+            assert_eq!(validate_str(jsonc), Ok(()), "{:?}", jsonc);
+        }
+    }
+
+    #[test]
+    fn test_invalid() {
+        use JsonCError::*;
+
+        let invalid = [
+            ("", UnexpectedEof { byte_offset: 0 }),
+            ("[1, 2", UnexpectedEof { byte_offset: 5 }),
+            ("[1 2]", UnexpectedToken { byte_offset: 3 }),
+            ("[1,,]", UnexpectedToken { byte_offset: 3 }),
+            ("[,]", UnexpectedToken { byte_offset: 1 }),
+            ("{,}", UnexpectedToken { byte_offset: 1 }),
+            ("{\"a\" 1}", UnexpectedToken { byte_offset: 5 }),
+            ("{1: 2}", UnexpectedToken { byte_offset: 1 }),
+            ("[1}", UnexpectedToken { byte_offset: 2 }),
+            ("[01]", UnexpectedToken { byte_offset: 1 }),
+            ("[1.]", UnexpectedToken { byte_offset: 1 }),
+            ("[undefined]", UnexpectedToken { byte_offset: 1 }),
+            ("1 2", TrailingContent { byte_offset: 2 }),
+            ("{\"a\": 1}garbage", TrailingContent { byte_offset: 8 }),
+            ("[] /* Comment */ []", TrailingContent { byte_offset: 17 }),
+            ("[1]]", TrailingContent { byte_offset: 3 }),
+            (
+                "[1] /* Comment",
+                UnterminatedBlockComment { byte_offset: 4 },
+            ),
+            ("[\"abc]", UnterminatedString { byte_offset: 1 }),
+            ("[\"a\\x\"]", InvalidEscape { byte_offset: 3 }),
+            ("[\"a\\u12G4\"]", InvalidEscape { byte_offset: 3 }),
+            ("[\"a\nb\"]", UnescapedControlCharacter { byte_offset: 3 }),
+        ];
+        for (jsonc, err) in invalid {
+            assert_eq!(validate_str(jsonc), Err(err), "{:?}", jsonc);
+        }
+    }
+
+    #[test]
+    fn test_validate_jsonc_first_error() {
+        let inputs = [
+            "",
+            "[1, 2",
+            "[1 2] 3",
+            "{\"a\" 1, \"b\": [1}",
+            "[\"\\x\\u12\"] /* Comment",
+            "[01, undefined, {]",
+            "{\"a\": 1}}",
+        ];
+        for jsonc in inputs {
+            let errors = validate_jsonc(jsonc);
+            assert_eq!(errors.first().cloned(), validate_str(jsonc).err());
+        }
+    }
+
+    #[test]
+    fn test_validate_jsonc_all_errors() {
+        use JsonCError::*;
+
+        assert_eq!(validate_jsonc("[1, 2,]"), []);
+        assert_eq!(
+            validate_jsonc("[1 2, 3 4]"),
+            [
+                UnexpectedToken { byte_offset: 3 },
+                UnexpectedToken { byte_offset: 8 },
+            ]
+        );
+        assert_eq!(
+            validate_jsonc("{\"a\": [1}, \"b\": 2} ]"),
+            [
+                UnexpectedToken { byte_offset: 8 },
+                TrailingContent { byte_offset: 9 },
+            ]
+        );
+        assert_eq!(
+            validate_jsonc("[\"\\x\\y\\u12\", 01, @]"),
+            [
+                InvalidEscape { byte_offset: 2 },
+                InvalidEscape { byte_offset: 4 },
+                InvalidEscape { byte_offset: 6 },
+                UnexpectedToken { byte_offset: 13 },
+                UnexpectedToken { byte_offset: 17 },
+            ]
+        );
+        assert_eq!(
+            validate_jsonc("[1] 2 undefined /* Comment"),
+            [
+                TrailingContent { byte_offset: 4 },
+                UnterminatedBlockComment { byte_offset: 16 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validator_reuse() {
+        let inputs = [
+            "[[[{\"a\": [1",
+            "[1, 2,]",
+            "{\"a\": [1}, \"b\": 2} ]",
+            "",
+            "{} // Comment",
+        ];
+
+        let mut validator = JsonCValidator::new();
+        for jsonc in inputs {
+            assert_eq!(
+                validator.validate(jsonc),
+                validate_jsonc(jsonc),
+                "{:?}",
+                jsonc
+            );
+        }
+    }
+}
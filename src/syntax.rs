@@ -0,0 +1,417 @@
+use std::ops::Range;
+
+use crate::{JsonCToJsonIter, Removed};
+
+/// Number of [JSON with Comments] specific tokens,
+/// see [`count_jsonc_tokens()`].
+///
+/// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Default, Debug)]
+pub struct JsonCSyntaxCount {
+    /// Number of line comments.
+    pub line_comments: usize,
+    /// Number of block comments.
+    pub block_comments: usize,
+    /// Number of trailing commas.
+    pub trailing_commas: usize,
+}
+
+impl JsonCSyntaxCount {
+    /// Returns `true` if all counts are zero.
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        (self.line_comments + self.block_comments + self.trailing_commas) == 0
+    }
+}
+
+/// Returns `true` if `jsonc` contains any [JSON with Comments] specific
+/// syntax, i.e. line comments, block comments, or trailing commas.
+///
+/// If `false` is returned, then [`jsonc_to_json()`] would return `jsonc`
+/// as is. The scan stops at the first encountered comment or trailing
+/// comma, and does not build any output.
+///
+/// # Example
+///
+/// ```rust
+/// use jsonc_to_json::has_jsonc_syntax;
+///
+/// assert!(has_jsonc_syntax("[1, 2, 3,]"));
+/// assert!(has_jsonc_syntax("[1, /* Comment */ 2]"));
+/// assert!(!has_jsonc_syntax("{\"url\": \"https://example.com\"}"));
+/// ```
+///
+/// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+/// [`jsonc_to_json()`]: crate::jsonc_to_json
+#[must_use]
+pub fn has_jsonc_syntax(jsonc: &str) -> bool {
+    let mut found = false;
+
+    let mut iter = JsonCToJsonIter::new(jsonc);
+    while !found && iter.next_with(&mut |_, _| found = true).is_some() {}
+
+    found
+}
+
+/// Returns `true` if `jsonc` does not contain any [JSON with Comments]
+/// specific syntax, i.e. if [`jsonc_to_json()`] would return `jsonc`
+/// as is, without removing anything.
+///
+/// This is the same as <code>![has_jsonc_syntax]\(jsonc)</code>, but instead
+/// of tokenizing `jsonc`, it is a single pass over the bytes, which only
+/// keeps track of whether it is inside a string. This makes it suitable
+/// for short-circuiting before more expensive processing.
+///
+/// **Note:** This is not the same as checking whether [`jsonc_to_json()`]
+/// returns <code>[Cow]::[Borrowed]</code>, as comments and trailing commas
+/// at the end of `jsonc` are removed without allocating, e.g. `[1] // Comment`
+/// returns `false`. Additionally, `jsonc` is not validated, i.e. `true` does
+/// not guarantee that `jsonc` is valid [JSON].
+///
+/// # Example
+///
+/// ```rust
+/// use jsonc_to_json::is_valid_json_without_conversion;
+///
+/// assert!(is_valid_json_without_conversion("{\"url\": \"https://example.com\"}"));
+/// assert!(!is_valid_json_without_conversion("[1, 2, 3,]"));
+/// assert!(!is_valid_json_without_conversion("[1, 2, 3] // Comment"));
+/// ```
+///
+/// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+/// [JSON]: https://www.json.org/json-en.html
+/// [`jsonc_to_json()`]: crate::jsonc_to_json
+/// [Cow]: std::borrow::Cow
+/// [Borrowed]: std::borrow::Cow::Borrowed
+#[must_use]
+pub fn is_valid_json_without_conversion(jsonc: &str) -> bool {
+    let bytes = jsonc.as_bytes();
+
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' => i = skip_string(bytes, i),
+            b'/' if matches!(bytes.get(i + 1), Some(b'/' | b'*')) => return false,
+            b',' => {
+                // A comma is a trailing comma if the next significant char
+                // is a closing delimiter, another comma, or the end of
+                // input. A comment following the comma is found next.
+                // Whitespace is the same as for the tokenizer, i.e. it
+                // includes non-ASCII whitespace
+                let next = jsonc[i + 1..].chars().find(|c| !c.is_whitespace());
+                if matches!(next, None | Some(',' | '}' | ']')) {
+                    return false;
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    true
+}
+
+/// Returns an upper bound of the length in bytes of the output of
+/// [`jsonc_to_json()`], e.g. for preallocating a buffer.
+///
+/// The estimate is never less than the actual length, but may be more.
+/// Comments are found using a single pass over the bytes, which only keeps
+/// track of whether it is inside a string, and their lengths are subtracted.
+/// Trailing commas are not subtracted, i.e. the estimate is off by the number
+/// of trailing commas. Use [`jsonc_to_json_savings()`] for the exact length,
+/// at the cost of tokenizing `jsonc`.
+///
+/// # Example
+///
+/// ```rust
+/// use jsonc_to_json::{jsonc_to_json, jsonc_to_json_estimate_output_len};
+///
+/// let jsonc = "[1, /* Comment */ 2,] // Line Comment";
+///
+/// assert_eq!(jsonc_to_json_estimate_output_len(jsonc), 9);
+/// assert_eq!(jsonc_to_json(jsonc).len(), 8);
+/// ```
+///
+/// [`jsonc_to_json()`]: crate::jsonc_to_json
+#[must_use]
+pub fn jsonc_to_json_estimate_output_len(jsonc: &str) -> usize {
+    let bytes = jsonc.as_bytes();
+
+    let mut len = bytes.len();
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' => i = skip_string(bytes, i),
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                let end = bytes[i..]
+                    .iter()
+                    .position(|&b| matches!(b, b'\n' | b'\r'))
+                    .map_or(bytes.len(), |n| i + n);
+                len -= end - i;
+                i = end;
+                continue;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                let end = bytes[i + 2..]
+                    .windows(2)
+                    .position(|w| w == b"*/")
+                    .map_or(bytes.len(), |n| i + 2 + n + 2);
+                len -= end - i;
+                i = end;
+                continue;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    len
+}
+
+/// Returns the index of the closing `"` of the string starting at
+/// `start`, or `bytes.len()` if the string is unterminated.
+fn skip_string(bytes: &[u8], start: usize) -> usize {
+    let mut i = start + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' => return i,
+            b'\\' => i += 2,
+            _ => i += 1,
+        }
+    }
+    bytes.len()
+}
+
+/// Returns the number of [JSON with Comments] specific tokens in `jsonc`,
+/// i.e. the number of tokens [`jsonc_to_json()`] would remove.
+///
+/// This does not build any output, see [`jsonc_to_json_with_stats()`]
+/// for counting while converting.
+///
+/// # Example
+///
+/// ```rust
+/// use jsonc_to_json::{count_jsonc_tokens, JsonCSyntaxCount};
+///
+/// let jsonc = "[1, /* Comment */ 2,,] // Line Comment";
+///
+/// assert_eq!(
+///     count_jsonc_tokens(jsonc),
+///     JsonCSyntaxCount {
+///         line_comments: 1,
+///         block_comments: 1,
+///         trailing_commas: 2,
+///     }
+/// );
+/// ```
+///
+/// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+/// [`jsonc_to_json()`]: crate::jsonc_to_json
+/// [`jsonc_to_json_with_stats()`]: crate::jsonc_to_json_with_stats
+#[must_use]
+pub fn count_jsonc_tokens(jsonc: &str) -> JsonCSyntaxCount {
+    let mut count = JsonCSyntaxCount::default();
+
+    let mut iter = JsonCToJsonIter::new(jsonc);
+    let mut on_removed = |removed, _: Range<usize>| match removed {
+        Removed::LineComment => count.line_comments += 1,
+        Removed::BlockComment => count.block_comments += 1,
+        Removed::TrailingComma { .. } => count.trailing_commas += 1,
+    };
+    while iter.next_with(&mut on_removed).is_some() {}
+
+    count
+}
+
+/// Number of bytes removed by [`jsonc_to_json()`] for each kind
+/// of [JSON with Comments] specific syntax,
+/// see [`jsonc_to_json_savings_detail()`].
+///
+/// **Note:** Despite the field names, all lengths are in bytes,
+/// i.e. a non-ASCII character in a comment counts as multiple.
+///
+/// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+/// [`jsonc_to_json()`]: crate::jsonc_to_json
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Default, Debug)]
+pub struct CharSavings {
+    /// Number of bytes of removed line comments.
+    pub line_comment_chars: usize,
+    /// Number of bytes of removed block comments.
+    pub block_comment_chars: usize,
+    /// Number of bytes of removed trailing commas.
+    pub trailing_comma_chars: usize,
+}
+
+impl CharSavings {
+    /// Returns the total number of bytes removed, i.e. the same
+    /// as [`jsonc_to_json_savings()`].
+    #[must_use]
+    #[inline]
+    pub fn total(&self) -> usize {
+        self.line_comment_chars + self.block_comment_chars + self.trailing_comma_chars
+    }
+}
+
+/// Returns the number of bytes [`jsonc_to_json()`] would remove from
+/// `jsonc`, i.e. `jsonc.len() - jsonc_to_json(jsonc).len()`, without
+/// building the output.
+///
+/// See [`jsonc_to_json_savings_detail()`] for a breakdown by kind.
+///
+/// # Example
+///
+/// ```rust
+/// use jsonc_to_json::{jsonc_to_json, jsonc_to_json_savings};
+///
+/// let jsonc = "[1, /* Comment */ 2,] // Line Comment";
+///
+/// assert_eq!(jsonc_to_json_savings(jsonc), 29);
+/// assert_eq!(jsonc_to_json_savings(jsonc), jsonc.len() - jsonc_to_json(jsonc).len());
+/// ```
+///
+/// [`jsonc_to_json()`]: crate::jsonc_to_json
+#[must_use]
+#[inline]
+pub fn jsonc_to_json_savings(jsonc: &str) -> usize {
+    jsonc_to_json_savings_detail(jsonc).total()
+}
+
+/// Same as [`jsonc_to_json_savings()`], but returns the number of bytes
+/// removed for each kind of [JSON with Comments] specific syntax.
+///
+/// # Example
+///
+/// ```rust
+/// use jsonc_to_json::{jsonc_to_json_savings_detail, CharSavings};
+///
+/// let jsonc = "[1, /* Comment */ 2,] // Line Comment";
+///
+/// assert_eq!(
+///     jsonc_to_json_savings_detail(jsonc),
+///     CharSavings {
+///         line_comment_chars: 15,
+///         block_comment_chars: 13,
+///         trailing_comma_chars: 1,
+///     }
+/// );
+/// ```
+///
+/// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+#[must_use]
+pub fn jsonc_to_json_savings_detail(jsonc: &str) -> CharSavings {
+    let mut savings = CharSavings::default();
+
+    let mut iter = JsonCToJsonIter::new(jsonc);
+    let mut on_removed = |removed, range: Range<usize>| match removed {
+        Removed::LineComment => savings.line_comment_chars += range.len(),
+        Removed::BlockComment => savings.block_comment_chars += range.len(),
+        Removed::TrailingComma { .. } => savings.trailing_comma_chars += range.len(),
+    };
+    while iter.next_with(&mut on_removed).is_some() {}
+
+    savings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::jsonc_to_json;
+
+    #[test]
+    fn test_has_jsonc_syntax() {
+        let inputs = [
+            "",
+            "[1, 2]",
+            "[1, 2] // Comment",
+            "\"/* string */\"",
+            "{\"a\": 1,}",
+            "/**/",
+        ];
+        for jsonc in inputs {
+            let has_syntax = jsonc_to_json(jsonc) != jsonc;
+            assert_eq!(has_jsonc_syntax(jsonc), has_syntax, "{:?}", jsonc);
+            assert_eq!(count_jsonc_tokens(jsonc).is_empty(), !has_syntax);
+        }
+    }
+
+    #[test]
+    fn test_is_valid_json_without_conversion() {
+        let inputs = [
+            "",
+            ",",
+            "/",
+            "[1, 2]",
+            "[1, 2] // Comment",
+            "[1, 2] /* Comment */",
+            "[1, 2 /",
+            "[1 / 2]",
+            "[1,,2]",
+            "[1, 2 ,\n\t",
+            "[[1],[2]]",
+            "[1, /**/ 2]",
+            "{\"a\": 1,}",
+            "{\"a\": 1, \"b\": 2}",
+            "\"/* string */\"",
+            "\"a\\\"//\"",
+            "\"a\\\\\",]",
+            "\"unterminated, ]",
+            "\"unterminated\\",
+            "{\"æøå\": \"日本語\", \"x\": 1}",
+            "[1,\u{a0}]",
+            "[1,\u{b}]",
+            "[1,\u{2028}]",
+            "{\"a\": 1,\u{3000}}",
+            "[1,\u{a0}2]",
+            "[1,\u{200b}]",
+            "[1,\u{feff}]",
+        ];
+        for jsonc in inputs {
+            assert_eq!(
+                is_valid_json_without_conversion(jsonc),
+                !has_jsonc_syntax(jsonc),
+                "{:?}",
+                jsonc
+            );
+        }
+    }
+
+    #[test]
+    fn test_estimate_output_len() {
+        let inputs = [
+            "",
+            "/",
+            "/*/",
+            "/**/",
+            "/* a * b ** / */",
+            "[1, 2] // Comment\r\n",
+            "[1, 2 /",
+            "[1,,2,]",
+            "{\"a//b\": \"/*c*/\", \"d\\\"//\": [1 , , 2 ,\n]}",
+            "{\"æ\": [1, /* ø */ 2,,], // 日本語\n}",
+            "\"unterminated // ]",
+            "/* unterminated",
+        ];
+        for jsonc in inputs {
+            let json = jsonc_to_json(jsonc);
+            let estimate = jsonc_to_json_estimate_output_len(jsonc);
+            assert!(estimate >= json.len(), "{:?}", jsonc);
+            assert!(estimate <= json.len() + jsonc.matches(',').count());
+        }
+    }
+
+    #[test]
+    fn test_savings() {
+        let inputs = [
+            "",
+            "[1, 2]",
+            "{\"æ\": [1, /* ø */ 2,,], // 日本語\n}",
+            "/* unterminated",
+        ];
+        for jsonc in inputs {
+            let expected = jsonc.len() - jsonc_to_json(jsonc).len();
+            assert_eq!(jsonc_to_json_savings(jsonc), expected, "{:?}", jsonc);
+        }
+    }
+}
@@ -0,0 +1,302 @@
+//! [`proptest`] strategies for generating [JSON with Comments],
+//! e.g. for property testing crates building on this crate.
+//!
+//! All strategies generate valid [JSON with Comments], i.e. the output
+//! of [`jsonc_to_json()`] is always valid [JSON], and
+//! [`jsonc_to_json_strict()`] always succeeds.
+//!
+//! _Requires the `testing` feature._
+//!
+//! # Example
+//!
+//! ```rust
+//! use jsonc_to_json::jsonc_to_json_strict;
+//! use jsonc_to_json::testing::arb_jsonc_string;
+//! use proptest::test_runner::TestRunner;
+//!
+//! let mut runner = TestRunner::default();
+//! runner
+//!     .run(&arb_jsonc_string(), |jsonc| {
+//!         assert!(jsonc_to_json_strict(&jsonc).is_ok());
+//!         Ok(())
+//!     })
+//!     .unwrap();
+//! ```
+//!
+//! [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+//! [JSON]: https://www.json.org/json-en.html
+//! [`proptest`]: https://docs.rs/proptest
+//! [`jsonc_to_json()`]: crate::jsonc_to_json
+//! [`jsonc_to_json_strict()`]: crate::jsonc_to_json_strict
+
+use proptest::prelude::*;
+
+/// Default depth of nested arrays and objects.
+const DEPTH: u32 = 4;
+
+/// Returns a strategy generating valid [JSON with Comments], i.e. valid
+/// [JSON] with comments and trailing commas inserted at random.
+///
+/// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+/// [JSON]: https://www.json.org/json-en.html
+pub fn arb_jsonc_string() -> impl Strategy<Value = String> {
+    arb_node(DEPTH, Syntax::ALL).prop_map(|node| node.to_string(true))
+}
+
+/// Returns a strategy generating valid [JSON] with comments inserted
+/// at random, but without trailing commas.
+///
+/// [JSON]: https://www.json.org/json-en.html
+pub fn arb_json_with_comments() -> impl Strategy<Value = String> {
+    let syntax = Syntax {
+        comments: true,
+        trailing_commas: false,
+    };
+    arb_node(DEPTH, syntax).prop_map(|node| node.to_string(true))
+}
+
+/// Returns a strategy generating valid [JSON] with trailing commas
+/// inserted at random, but without comments.
+///
+/// [JSON]: https://www.json.org/json-en.html
+pub fn arb_trailing_commas() -> impl Strategy<Value = String> {
+    let syntax = Syntax {
+        comments: false,
+        trailing_commas: true,
+    };
+    arb_node(DEPTH, syntax).prop_map(|node| node.to_string(true))
+}
+
+/// Same as [`arb_jsonc_string()`], but with arrays and objects nested
+/// up to `depth` levels deep.
+pub fn arb_nested_jsonc(depth: u32) -> impl Strategy<Value = String> {
+    arb_node(depth, Syntax::ALL).prop_map(|node| node.to_string(true))
+}
+
+/// Which [JSON with Comments] specific syntax to generate.
+///
+/// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+#[derive(Clone, Copy, Debug)]
+struct Syntax {
+    comments: bool,
+    trailing_commas: bool,
+}
+
+impl Syntax {
+    const ALL: Self = Self {
+        comments: true,
+        trailing_commas: true,
+    };
+}
+
+/// Whitespace or comment inserted before a token.
+#[derive(Clone, Debug)]
+enum Trivia {
+    None,
+    Space(&'static str),
+    LineComment(String),
+    BlockComment(String),
+}
+
+#[derive(Clone, Debug)]
+enum Value {
+    Scalar(String),
+    Array(Vec<Node>),
+    Object(Vec<(Trivia, String, Node)>),
+}
+
+/// Value preceded by trivia. Arrays and objects additionally
+/// contain the trivia before their closing bracket.
+#[derive(Clone, Debug)]
+struct Node {
+    before: Trivia,
+    value: Value,
+    trailing_comma: bool,
+    before_end: Trivia,
+}
+
+impl Node {
+    /// Renders the node, with or without the [JSON with Comments]
+    /// specific syntax.
+    ///
+    /// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+    fn to_string(&self, jsonc: bool) -> String {
+        let mut s = String::new();
+        self.render(jsonc, &mut s);
+        s
+    }
+
+    fn render(&self, jsonc: bool, s: &mut String) {
+        self.before.render(jsonc, s);
+        match &self.value {
+            Value::Scalar(value) => s.push_str(value),
+            Value::Array(values) => {
+                s.push('[');
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        s.push(',');
+                    }
+                    value.render(jsonc, s);
+                }
+                self.render_end(jsonc, !values.is_empty(), s);
+                s.push(']');
+            }
+            Value::Object(entries) => {
+                s.push('{');
+                for (i, (before_key, key, value)) in entries.iter().enumerate() {
+                    if i > 0 {
+                        s.push(',');
+                    }
+                    before_key.render(jsonc, s);
+                    s.push_str(key);
+                    s.push(':');
+                    value.render(jsonc, s);
+                }
+                self.render_end(jsonc, !entries.is_empty(), s);
+                s.push('}');
+            }
+        }
+    }
+
+    fn render_end(&self, jsonc: bool, non_empty: bool, s: &mut String) {
+        if jsonc && non_empty && self.trailing_comma {
+            s.push(',');
+        }
+        self.before_end.render(jsonc, s);
+    }
+}
+
+impl Trivia {
+    fn render(&self, jsonc: bool, s: &mut String) {
+        match self {
+            Self::None => {}
+            Self::Space(space) => s.push_str(space),
+            Self::LineComment(comment) if jsonc => {
+                s.push_str("//");
+                s.push_str(comment);
+                s.push('\n');
+            }
+            Self::BlockComment(comment) if jsonc => {
+                s.push_str("/*");
+                s.push_str(comment);
+                s.push_str("*/");
+            }
+            Self::LineComment(_) | Self::BlockComment(_) => s.push(' '),
+        }
+    }
+}
+
+fn arb_trivia(comments: bool) -> BoxedStrategy<Trivia> {
+    let space = prop_oneof![
+        Just(Trivia::None),
+        prop_oneof![Just(" "), Just("\n"), Just("\r\n  "), Just("\t")].prop_map(Trivia::Space),
+    ];
+    if !comments {
+        return space.boxed();
+    }
+
+    // Comments contain characters, which could be
+    // mistaken for syntax, e.g. `//` inside block comments
+    let text = "[a-z0-9 /*\",\\[\\]{}]{0,12}";
+    prop_oneof![
+        2 => space,
+        1 => text.prop_map(Trivia::LineComment),
+        1 => text
+            .prop_filter("block comment ended early", |s| !s.contains("*/"))
+            .prop_map(Trivia::BlockComment),
+    ]
+    .boxed()
+}
+
+fn arb_string() -> impl Strategy<Value = String> {
+    // Strings contain characters, which could be mistaken
+    // for comments and trailing commas
+    let fragment = prop_oneof![
+        "[a-zA-Z0-9 ,/*\\[\\]{}æø日]{1,4}",
+        Just("\\\"".to_owned()),
+        Just("\\\\".to_owned()),
+        Just("\\/".to_owned()),
+        Just("\\n".to_owned()),
+        Just("\\u00e6".to_owned()),
+    ];
+    prop::collection::vec(fragment, 0..4)
+        .prop_map(|fragments| format!("\"{}\"", fragments.concat()))
+}
+
+fn arb_scalar() -> impl Strategy<Value = String> {
+    prop_oneof![
+        Just("null".to_owned()),
+        Just("true".to_owned()),
+        Just("false".to_owned()),
+        any::<i32>().prop_map(|n| n.to_string()),
+        (any::<i16>(), 0..1000_u16, -20..20_i8).prop_map(|(i, f, e)| format!("{}.{}e{}", i, f, e)),
+        arb_string(),
+    ]
+}
+
+fn arb_node(depth: u32, syntax: Syntax) -> impl Strategy<Value = Node> {
+    let trivia = move || arb_trivia(syntax.comments);
+    let trailing_comma = move || {
+        if syntax.trailing_commas {
+            any::<bool>().boxed()
+        } else {
+            Just(false).boxed()
+        }
+    };
+
+    let leaf = (trivia(), arb_scalar()).prop_map(|(before, value)| Node {
+        before,
+        value: Value::Scalar(value),
+        trailing_comma: false,
+        before_end: Trivia::None,
+    });
+
+    leaf.prop_recursive(depth, 64, 6, move |inner| {
+        let array = prop::collection::vec(inner.clone(), 0..6).prop_map(Value::Array);
+        let object =
+            prop::collection::vec((trivia(), arb_string(), inner), 0..6).prop_map(Value::Object);
+        (
+            trivia(),
+            prop_oneof![array, object],
+            trailing_comma(),
+            trivia(),
+        )
+            .prop_map(|(before, value, trailing_comma, before_end)| Node {
+                before,
+                value,
+                trailing_comma,
+                before_end,
+            })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{jsonc_to_json, jsonc_to_json_strict};
+
+    proptest! {
+        #[test]
+        fn test_stripped_equals_json((jsonc, json) in arb_node(DEPTH, Syntax::ALL)
+            .prop_map(|node| (node.to_string(true), node.to_string(false))))
+        {
+            prop_assert!(jsonc_to_json_strict(&jsonc).is_ok(), "{:?}", jsonc);
+
+            let stripped = serde_json::from_str::<serde_json::Value>(&jsonc_to_json(&jsonc));
+            let expected = serde_json::from_str::<serde_json::Value>(&json);
+            prop_assert_eq!(stripped.unwrap(), expected.unwrap());
+        }
+
+        #[test]
+        fn test_strategies_are_valid(
+            a in arb_json_with_comments(),
+            b in arb_trailing_commas(),
+            c in arb_nested_jsonc(8),
+        ) {
+            for jsonc in [a, b, c] {
+                prop_assert!(jsonc_to_json_strict(&jsonc).is_ok(), "{:?}", jsonc);
+            }
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use std::iter::FusedIterator;
+use std::ops::Range;
+
+use any_lexer::JsonCToken as LexerToken;
+
+use crate::tokens::JsonCTokens;
+
+/// Kind of token in [JSON with Comments], see [`jsonc_span_iter()`].
+///
+/// Tokens are ordered by the order of the variants, e.g. for
+/// use as keys in a [`BTreeMap`].
+///
+/// # Example
+///
+/// ```rust
+/// use std::collections::BTreeMap;
+/// use jsonc_to_json::{jsonc_span_iter, JsonCToken};
+///
+/// let jsonc = "[1, /* Comment */ 2]";
+///
+/// let mut histogram = BTreeMap::new();
+/// for (tok, _range) in jsonc_span_iter(jsonc) {
+///     *histogram.entry(tok).or_insert(0) += 1;
+/// }
+/// assert_eq!(
+///     histogram.into_iter().collect::<Vec<_>>(),
+///     [
+///         (JsonCToken::Space, 2),
+///         (JsonCToken::BlockComment, 1),
+///         (JsonCToken::Number, 2),
+///         (JsonCToken::Punct, 1),
+///         (JsonCToken::Delim, 2),
+///     ]
+/// );
+/// ```
+///
+/// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+/// [`BTreeMap`]: std::collections::BTreeMap
+#[non_exhaustive]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug)]
+pub enum JsonCToken {
+    /// Whitespace, e.g. spaces and newlines.
+    Space,
+    /// Line comment, e.g. `// Comment`, excluding the trailing newline.
+    LineComment,
+    /// Block comment, e.g. `/* Comment */`.
+    BlockComment,
+    /// String, e.g. `"foo"`, including the quotes.
+    String,
+    /// Number, e.g. `123` or `-1.5e3`.
+    Number,
+    /// `null`.
+    Null,
+    /// `true`.
+    True,
+    /// `false`.
+    False,
+    /// Punctuation, i.e. `,` and `:`.
+    Punct,
+    /// Delimiter, i.e. `{`, `}`, `[`, and `]`.
+    Delim,
+    /// Anything else, e.g. `undefined`.
+    Unknown,
+}
+
+impl From<LexerToken> for JsonCToken {
+    #[inline]
+    fn from(tok: LexerToken) -> Self {
+        match tok {
+            LexerToken::Space => Self::Space,
+            LexerToken::LineComment => Self::LineComment,
+            LexerToken::BlockComment => Self::BlockComment,
+            LexerToken::String => Self::String,
+            LexerToken::Number => Self::Number,
+            LexerToken::Null => Self::Null,
+            LexerToken::True => Self::True,
+            LexerToken::False => Self::False,
+            LexerToken::Punct => Self::Punct,
+            LexerToken::Delim => Self::Delim,
+            LexerToken::Unknown => Self::Unknown,
+        }
+    }
+}
+
+/// Returns an [`Iterator`] over every token in `jsonc`, including
+/// whitespace and comments, along with its byte range in `jsonc`.
+///
+/// This is the same tokenization used by [`jsonc_to_json()`], which
+/// is useful for e.g. syntax highlighting. The ranges of all tokens
+/// together cover the entire input.
+///
+/// # Example
+///
+/// ```rust
+/// use jsonc_to_json::{jsonc_span_iter, JsonCToken};
+///
+/// let jsonc = "[1, /* Comment */ 2]";
+///
+/// let tokens = jsonc_span_iter(jsonc).collect::<Vec<_>>();
+/// assert_eq!(
+///     tokens,
+///     [
+///         (JsonCToken::Delim, 0..1),
+///         (JsonCToken::Number, 1..2),
+///         (JsonCToken::Punct, 2..3),
+///         (JsonCToken::Space, 3..4),
+///         (JsonCToken::BlockComment, 4..17),
+///         (JsonCToken::Space, 17..18),
+///         (JsonCToken::Number, 18..19),
+///         (JsonCToken::Delim, 19..20),
+///     ]
+/// );
+/// ```
+///
+/// [`jsonc_to_json()`]: crate::jsonc_to_json
+#[inline]
+pub fn jsonc_span_iter(
+    jsonc: &str,
+) -> impl FusedIterator<Item = (JsonCToken, Range<usize>)> + Clone + '_ {
+    JsonCTokens::new(jsonc).map(|(tok, span)| (tok.into(), span.range()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_span_iter_covers_input() {
+        let jsonc = "{\"a\": [1, /* b */ 2,], // c\n\"d\": undefined}";
+
+        let mut end = 0;
+        for (_tok, range) in jsonc_span_iter(jsonc) {
+            assert_eq!(range.start, end);
+            end = range.end;
+        }
+        assert_eq!(end, jsonc.len());
+    }
+}
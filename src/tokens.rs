@@ -0,0 +1,152 @@
+use std::iter::FusedIterator;
+
+use any_lexer::{JsonCLexer, JsonCToken, Lexer, TokenSpan};
+
+/// [`JsonCLexer`] wrapper, which applies the token-level options
+/// of [`JsonCConfig`].
+///
+/// [`JsonCConfig`]: crate::JsonCConfig
+#[derive(Clone, Debug)]
+pub(crate) struct JsonCTokens<'jsonc> {
+    lexer: JsonCLexer<'jsonc>,
+    nested_block_comments: bool,
+}
+
+impl<'jsonc> JsonCTokens<'jsonc> {
+    #[inline]
+    pub(crate) fn new(jsonc: &'jsonc str) -> Self {
+        Self {
+            lexer: JsonCLexer::new(jsonc),
+            nested_block_comments: false,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn nested_block_comments(mut self, nested: bool) -> Self {
+        self.nested_block_comments = nested;
+        self
+    }
+
+    /// Returns `true` if block comments can be nested.
+    #[inline]
+    pub(crate) fn is_nested_block_comments(&self) -> bool {
+        self.nested_block_comments
+    }
+
+    /// Returns the whole input text.
+    #[inline]
+    pub(crate) fn text(&self) -> &'jsonc str {
+        self.lexer.scanner().text()
+    }
+
+    /// Returns the text that has not been tokenized yet.
+    #[inline]
+    pub(crate) fn remaining_text(&self) -> &'jsonc str {
+        self.lexer.scanner().remaining_text()
+    }
+
+    /// Continues tokenizing from byte offset `pos`.
+    #[inline]
+    pub(crate) fn set_cursor_pos(&mut self, pos: usize) {
+        self.lexer.set_cursor_pos(pos);
+    }
+
+    /// Returns the end of a block comment starting at `start`, i.e. after
+    /// the first `*/`.
+    ///
+    /// The lexer does not end a block comment at `**/`, e.g. `/* a **/`,
+    /// so the end of every block comment is rescanned.
+    fn block_comment_end(&self, start: usize) -> usize {
+        let text = self.text();
+        match text[(start + 2)..].find("*/") {
+            Some(i) => start + 2 + i + 2,
+            // Unterminated block comments extend to the end of the input
+            None => text.len(),
+        }
+    }
+
+    /// Extends a block comment starting at `start` to include nested block
+    /// comments, returning the end of the outermost block comment.
+    fn nested_block_comment_end(&self, start: usize) -> usize {
+        let bytes = self.text().as_bytes();
+
+        let mut depth = 0_usize;
+        let mut i = start;
+        while i < bytes.len() {
+            match &bytes[i..] {
+                [b'/', b'*', ..] => {
+                    depth += 1;
+                    i += 2;
+                }
+                [b'*', b'/', ..] => {
+                    depth -= 1;
+                    i += 2;
+                    if depth == 0 {
+                        return i;
+                    }
+                }
+                _ => i += 1,
+            }
+        }
+
+        // Unterminated block comments extend to the end of the input
+        bytes.len()
+    }
+}
+
+impl<'jsonc> Iterator for JsonCTokens<'jsonc> {
+    type Item = (JsonCToken, TokenSpan<'jsonc>);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (tok, span) = self.lexer.next_token()?;
+        match tok {
+            JsonCToken::BlockComment => {
+                let end = if self.nested_block_comments {
+                    self.nested_block_comment_end(span.start())
+                } else {
+                    self.block_comment_end(span.start())
+                };
+                if end == span.end() {
+                    return Some((tok, span));
+                }
+                self.lexer.set_cursor_pos(end);
+                Some((tok, TokenSpan::new(self.text(), span.start()..end)))
+            }
+            _ => Some((tok, span)),
+        }
+    }
+}
+
+impl FusedIterator for JsonCTokens<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::{jsonc_to_json, jsonc_to_json_from_bytes, jsonc_to_json_strict};
+
+    #[test]
+    fn test_block_comment_double_star_end() {
+        let cases = [
+            ("[/** doc **/ 1]", "[ 1]"),
+            ("[1, /* a **/ 2]", "[1,  2]"),
+            ("[1, /***/ 2,]", "[1,  2]"),
+            ("[1, /**/ 2]", "[1,  2]"),
+            ("[1, /*/ 2 */ 3]", "[1,  3]"),
+            ("[1] /** unterminated *", "[1] "),
+        ];
+        for (jsonc, expected) in cases {
+            assert_eq!(jsonc_to_json(jsonc), expected, "{:?}", jsonc);
+            assert_eq!(
+                jsonc_to_json_from_bytes(jsonc.as_bytes()).unwrap(),
+                expected.as_bytes(),
+                "{:?}",
+                jsonc
+            );
+        }
+
+        assert_eq!(
+            jsonc_to_json_strict("/** doc **/ [1, /***/]").unwrap(),
+            " [1 ]"
+        );
+    }
+}
@@ -0,0 +1,97 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::ops::Deref;
+
+use crate::{jsonc_to_json_strict, JsonCError};
+
+/// [JSON] which is guaranteed to be valid, i.e. it has been converted
+/// from valid [JSON with Comments] using [`jsonc_to_json_strict()`].
+///
+/// This allows passing `ValidJson` to APIs that demand valid [JSON]
+/// without needing to validate it again.
+///
+/// # Example
+///
+/// ```rust
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use jsonc_to_json::ValidJson;
+///
+/// let jsonc = "{\"arr\": [1, 2,/* Comment */ 3, 4,]}// Line Comment";
+///
+/// let json = ValidJson::try_from(jsonc)?;
+/// assert_eq!(json.as_str(), "{\"arr\": [1, 2, 3, 4]}");
+///
+/// assert!(ValidJson::try_from("{\"arr\": [1, 2 3]}").is_err());
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+/// [JSON]: https://www.json.org/json-en.html
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct ValidJson<'jsonc>(Cow<'jsonc, str>);
+
+impl<'jsonc> ValidJson<'jsonc> {
+    /// Wraps `json`, which the caller guarantees to be valid.
+    #[cfg(feature = "serde_json")]
+    #[inline]
+    pub(crate) fn from_valid(json: Cow<'jsonc, str>) -> Self {
+        Self(json)
+    }
+
+    /// Returns the valid [JSON] as a string slice.
+    ///
+    /// [JSON]: https://www.json.org/json-en.html
+    #[must_use]
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the inner <code>[Cow]<[str]></code>.
+    #[must_use]
+    #[inline]
+    pub fn into_inner(self) -> Cow<'jsonc, str> {
+        self.0
+    }
+}
+
+impl<'jsonc> TryFrom<&'jsonc str> for ValidJson<'jsonc> {
+    type Error = JsonCError;
+
+    /// See [`jsonc_to_json_strict()`] for more information.
+    #[inline]
+    fn try_from(jsonc: &'jsonc str) -> Result<Self, Self::Error> {
+        jsonc_to_json_strict(jsonc).map(Self)
+    }
+}
+
+impl Deref for ValidJson<'_> {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl AsRef<str> for ValidJson<'_> {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for ValidJson<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<ValidJson<'_>> for String {
+    #[inline]
+    fn from(json: ValidJson<'_>) -> Self {
+        json.0.into_owned()
+    }
+}
@@ -0,0 +1,181 @@
+use std::error;
+use std::fmt;
+
+use serde_json::Value;
+
+use crate::{jsonc_to_json_strict, JsonCError, JsonCToJsonIter, ValidJson};
+
+/// Error returned by [`jsonc_to_value()`].
+#[derive(Debug)]
+pub enum JsonCValueError {
+    /// The input is not valid [JSON with Comments].
+    ///
+    /// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+    JsonC(JsonCError),
+    /// The converted [JSON] could not be parsed by [`serde_json`],
+    /// e.g. due to a number being out of range.
+    ///
+    /// [JSON]: https://www.json.org/json-en.html
+    /// [`serde_json`]: https://docs.rs/serde_json
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for JsonCValueError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::JsonC(err) => err.fmt(f),
+            Self::Json(err) => err.fmt(f),
+        }
+    }
+}
+
+impl error::Error for JsonCValueError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::JsonC(err) => Some(err),
+            Self::Json(err) => Some(err),
+        }
+    }
+}
+
+impl From<JsonCError> for JsonCValueError {
+    #[inline]
+    fn from(err: JsonCError) -> Self {
+        Self::JsonC(err)
+    }
+}
+
+impl From<serde_json::Error> for JsonCValueError {
+    #[inline]
+    fn from(err: serde_json::Error) -> Self {
+        Self::Json(err)
+    }
+}
+
+/// Converts [JSON with Comments] using [`jsonc_to_json_strict()`],
+/// and then parses the [JSON] into a [`serde_json::Value`].
+///
+/// _Requires the `serde_json` feature._
+///
+/// # Example
+///
+/// ```rust
+/// use jsonc_to_json::jsonc_to_value;
+///
+/// let jsonc = "{\"arr\": [1, 2,/* Comment */ 3, 4,]}// Line Comment";
+///
+/// let value = jsonc_to_value(jsonc)?;
+/// assert_eq!(value["arr"][2], 3);
+/// # Ok::<(), jsonc_to_json::JsonCValueError>(())
+/// ```
+///
+/// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+/// [JSON]: https://www.json.org/json-en.html
+/// [`serde_json::Value`]: https://docs.rs/serde_json/latest/serde_json/enum.Value.html
+pub fn jsonc_to_value(jsonc: &str) -> Result<Value, JsonCValueError> {
+    let json = jsonc_to_json_strict(jsonc)?;
+    Ok(serde_json::from_str(&json)?)
+}
+
+impl From<Value> for ValidJson<'static> {
+    /// Serializes `value` into [JSON] without whitespace.
+    ///
+    /// _Requires the `serde_json` feature._
+    ///
+    /// [JSON]: https://www.json.org/json-en.html
+    #[inline]
+    fn from(value: Value) -> Self {
+        Self::from_valid(value.to_string().into())
+    }
+}
+
+impl TryFrom<ValidJson<'_>> for Value {
+    type Error = serde_json::Error;
+
+    /// Parses the valid [JSON] into a [`serde_json::Value`].
+    ///
+    /// This can still fail, e.g. if a number is out of range.
+    ///
+    /// _Requires the `serde_json` feature._
+    ///
+    /// [JSON]: https://www.json.org/json-en.html
+    /// [`serde_json::Value`]: https://docs.rs/serde_json/latest/serde_json/enum.Value.html
+    #[inline]
+    fn try_from(json: ValidJson<'_>) -> Result<Self, Self::Error> {
+        serde_json::from_str(json.as_str())
+    }
+}
+
+impl TryFrom<JsonCToJsonIter<'_>> for Value {
+    type Error = serde_json::Error;
+
+    /// Collects the remaining converted [JSON], and parses it into
+    /// a [`serde_json::Value`].
+    ///
+    /// **Note:** The [JSON with Comments] is not validated, i.e. invalid
+    /// input results in a [`serde_json::Error`] instead of a [`JsonCError`].
+    /// Use [`jsonc_to_value()`] for validating the input.
+    ///
+    /// _Requires the `serde_json` feature._
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use jsonc_to_json::jsonc_to_json_iter;
+    /// use serde_json::Value;
+    ///
+    /// let jsonc = "{\"arr\": [1, 2,/* Comment */ 3, 4,]}// Line Comment";
+    ///
+    /// let value = Value::try_from(jsonc_to_json_iter(jsonc))?;
+    /// assert_eq!(value["arr"][2], 3);
+    /// # Ok::<(), serde_json::Error>(())
+    /// ```
+    ///
+    /// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+    /// [JSON]: https://www.json.org/json-en.html
+    /// [`serde_json::Value`]: https://docs.rs/serde_json/latest/serde_json/enum.Value.html
+    /// [`serde_json::Error`]: https://docs.rs/serde_json/latest/serde_json/struct.Error.html
+    #[inline]
+    fn try_from(iter: JsonCToJsonIter<'_>) -> Result<Self, Self::Error> {
+        serde_json::from_str(&iter.collect::<String>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_jsonc_to_value_errors() {
+        let err = jsonc_to_value("[1 2]").unwrap_err();
+        assert!(matches!(
+            err,
+            JsonCValueError::JsonC(JsonCError::UnexpectedToken { byte_offset: 3 })
+        ));
+
+        let err = jsonc_to_value("[1e400]").unwrap_err();
+        assert!(matches!(err, JsonCValueError::Json(_)));
+    }
+
+    #[test]
+    fn test_valid_json_value() {
+        let json = ValidJson::try_from("{\"a\": [1, 2,], /* Comment */}").unwrap();
+        let value = Value::try_from(json).unwrap();
+        assert_eq!(value, serde_json::json!({"a": [1, 2]}));
+        assert_eq!(ValidJson::from(value).as_str(), "{\"a\":[1,2]}");
+    }
+
+    #[test]
+    fn test_iter_try_into_value() {
+        let mut iter = JsonCToJsonIter::new("[1, /* Comment */ 2,] {\"a\": 3,}");
+        assert_eq!(iter.next(), Some("[1, "));
+
+        // The remaining output is `2] {"a": 3}`
+        let err = Value::try_from(iter).unwrap_err();
+        assert!(err.is_syntax());
+
+        let iter = JsonCToJsonIter::new("{\"a\": [1, 2,], // Comment\n}");
+        let value: Value = iter.try_into().unwrap();
+        assert_eq!(value, serde_json::json!({"a": [1, 2]}));
+    }
+}
@@ -0,0 +1,367 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use any_lexer::JsonCToken;
+
+use crate::offsets::OffsetMap;
+use crate::tokens::JsonCTokens;
+use crate::{jsonc_to_json, JsonCError};
+
+/// Specifies how references to unknown variables are handled,
+/// see [`jsonc_expand_vars_with()`].
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Default, Debug)]
+pub enum UnknownVar {
+    /// Leave the `${VAR}` reference as-is.
+    #[default]
+    Preserve,
+    /// Substitute the `${VAR}` reference with an empty string.
+    Empty,
+    /// Return [`JsonCError::UnknownVariable`].
+    Error,
+}
+
+/// Substitutes `${VAR}` references inside strings with the
+/// corresponding value in `vars`, and then converts the
+/// [JSON with Comments] into [JSON] using [`jsonc_to_json()`].
+///
+/// References to unknown variables are left as-is, see
+/// [`jsonc_expand_vars_with()`] for alternatives.
+///
+/// # Example
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use jsonc_to_json::jsonc_expand_vars;
+///
+/// let jsonc = r#"{"cache": "${HOME}/.cache", /* ${HOME} */ "tmp": "${TMP}",}"#;
+///
+/// let vars = HashMap::from([("HOME", "/home/user")]);
+///
+/// let json = jsonc_expand_vars(jsonc, &vars);
+/// assert_eq!(json, r#"{"cache": "/home/user/.cache",  "tmp": "${TMP}"}"#);
+/// ```
+///
+/// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+/// [JSON]: https://www.json.org/json-en.html
+/// [`jsonc_to_json()`]: crate::jsonc_to_json
+#[must_use = "this returns the converted JSON; dropping it does nothing"]
+pub fn jsonc_expand_vars<'jsonc>(
+    jsonc: &'jsonc str,
+    vars: &HashMap<&str, &str>,
+) -> Cow<'jsonc, str> {
+    match jsonc_expand_vars_with(jsonc, vars, UnknownVar::Preserve) {
+        Ok(json) => json,
+        Err(_) => unreachable!(),
+    }
+}
+
+/// Same as [`jsonc_expand_vars()`], but references to unknown
+/// variables are handled according to `unknown`.
+///
+/// Only `${VAR}` references inside strings are substituted, i.e.
+/// references inside comments are ignored. Values are escaped,
+/// such that e.g. `"` and `\` in a value produces a valid string.
+///
+/// Returns [`JsonCError::UnknownVariable`] if `unknown` is
+/// [`UnknownVar::Error`] and a variable is not in `vars`.
+///
+/// # Example
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use jsonc_to_json::{jsonc_expand_vars_with, JsonCError, UnknownVar};
+///
+/// let jsonc = r#"["${A}", "${B}"]"#;
+///
+/// let vars = HashMap::from([("A", "\"a\"")]);
+///
+/// let json = jsonc_expand_vars_with(jsonc, &vars, UnknownVar::Empty);
+/// assert_eq!(json.unwrap(), r#"["\"a\"", ""]"#);
+///
+/// let err = jsonc_expand_vars_with(jsonc, &vars, UnknownVar::Error);
+/// assert_eq!(err, Err(JsonCError::UnknownVariable { byte_offset: 10 }));
+/// ```
+pub fn jsonc_expand_vars_with<'jsonc>(
+    jsonc: &'jsonc str,
+    vars: &HashMap<&str, &str>,
+    unknown: UnknownVar,
+) -> Result<Cow<'jsonc, str>, JsonCError> {
+    expand_vars(
+        jsonc,
+        |name| vars.get(name).map(|&value| value.into()),
+        unknown,
+    )
+}
+
+/// Reads the [JSON with Comments] file at `path`, substitutes `${VAR}`
+/// references inside strings with the value of the corresponding
+/// environment variable, and then converts it into [JSON] using
+/// [`jsonc_to_json()`].
+///
+/// References to environment variables, which are not set or not
+/// valid unicode, are substituted with an empty string, i.e. the same
+/// as `env::var(VAR).unwrap_or_default()`. Use
+/// [`jsonc_to_json_from_path_with_env_or()`] to handle them otherwise.
+///
+/// See [`jsonc_expand_vars_with()`] for more information.
+///
+/// # Errors
+///
+/// Returns an error if `path` cannot be read or does not contain
+/// valid UTF-8.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use jsonc_to_json::jsonc_to_json_from_path_with_env;
+///
+/// // e.g. `{"cache": "${HOME}/.cache", /* Comment */}`
+/// let json = jsonc_to_json_from_path_with_env("config.jsonc")?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+///
+/// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+/// [JSON]: https://www.json.org/json-en.html
+/// [`jsonc_to_json()`]: crate::jsonc_to_json
+#[inline]
+pub fn jsonc_to_json_from_path_with_env(path: impl AsRef<Path>) -> io::Result<String> {
+    jsonc_to_json_from_path_with_env_or(path, UnknownVar::Empty)
+}
+
+/// Same as [`jsonc_to_json_from_path_with_env()`], but references to
+/// environment variables, which are not set or not valid unicode, are
+/// handled according to `unknown`.
+///
+/// # Errors
+///
+/// Returns an error if `path` cannot be read or does not contain
+/// valid UTF-8. If `unknown` is [`UnknownVar::Error`] and a variable
+/// is not set, then an error of kind [`InvalidData`] is returned,
+/// which wraps [`JsonCError::UnknownVariable`].
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use jsonc_to_json::{jsonc_to_json_from_path_with_env_or, UnknownVar};
+///
+/// let json = jsonc_to_json_from_path_with_env_or("config.jsonc", UnknownVar::Error)?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+///
+/// [`InvalidData`]: io::ErrorKind::InvalidData
+pub fn jsonc_to_json_from_path_with_env_or(
+    path: impl AsRef<Path>,
+    unknown: UnknownVar,
+) -> io::Result<String> {
+    let jsonc = fs::read_to_string(path)?;
+    let json = expand_vars(&jsonc, |name| env::var(name).ok().map(Cow::Owned), unknown)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    Ok(json.into_owned())
+}
+
+fn expand_vars<'jsonc, 'value, F>(
+    jsonc: &'jsonc str,
+    lookup: F,
+    unknown: UnknownVar,
+) -> Result<Cow<'jsonc, str>, JsonCError>
+where
+    F: FnMut(&str) -> Option<Cow<'value, str>>,
+{
+    Ok(match substitute_vars(jsonc, lookup, unknown)? {
+        Cow::Borrowed(jsonc) => jsonc_to_json(jsonc),
+        Cow::Owned(expanded) => Cow::Owned(jsonc_to_json(&expanded).into_owned()),
+    })
+}
+
+/// Substitutes `${VAR}` references inside strings with the value
+/// returned by `lookup`, without converting the [JSON with Comments].
+///
+/// [JSON with Comments]: https://code.visualstudio.com/docs/languages/json#_json-with-comments
+pub(crate) fn substitute_vars<'jsonc, 'value, F>(
+    jsonc: &'jsonc str,
+    lookup: F,
+    unknown: UnknownVar,
+) -> Result<Cow<'jsonc, str>, JsonCError>
+where
+    F: FnMut(&str) -> Option<Cow<'value, str>>,
+{
+    let mut expanded = String::new();
+    if substitute_vars_into(jsonc, lookup, unknown, &mut expanded, None)? {
+        Ok(Cow::Owned(expanded))
+    } else {
+        Ok(Cow::Borrowed(jsonc))
+    }
+}
+
+/// Same as [`substitute_vars()`], but appends the output to `expanded`.
+/// Returns `false` if nothing was substituted, in which case nothing
+/// is appended.
+///
+/// If `offsets` is `Some`, then it is filled with the mapping of byte
+/// offsets in the output back into `jsonc`.
+pub(crate) fn substitute_vars_into<'value, F>(
+    jsonc: &str,
+    mut lookup: F,
+    unknown: UnknownVar,
+    expanded: &mut String,
+    mut offsets: Option<&mut OffsetMap>,
+) -> Result<bool, JsonCError>
+where
+    F: FnMut(&str) -> Option<Cow<'value, str>>,
+{
+    let output_start = expanded.len();
+    // End of the part of `jsonc`, which has been copied into `expanded`
+    let mut copied = 0;
+    let mut substituted = false;
+
+    for (tok, span) in JsonCTokens::new(jsonc) {
+        if tok != JsonCToken::String {
+            continue;
+        }
+
+        let s = span.as_str();
+        let mut rest = s;
+        while let Some(i) = rest.find("${") {
+            let start = span.start() + (s.len() - rest.len()) + i;
+            let name_and_rest = &rest[(i + 2)..];
+            let Some(name_len) = name_and_rest.find('}') else {
+                break;
+            };
+            let name = &name_and_rest[..name_len];
+            let end = start + 2 + name_len + 1;
+            rest = &name_and_rest[(name_len + 1)..];
+
+            let value = match (lookup(name), unknown) {
+                (Some(value), _) => value,
+                (None, UnknownVar::Preserve) => continue,
+                (None, UnknownVar::Empty) => Cow::Borrowed(""),
+                (None, UnknownVar::Error) => {
+                    return Err(JsonCError::UnknownVariable { byte_offset: start });
+                }
+            };
+
+            if let Some(offsets) = offsets.as_deref_mut() {
+                offsets.push(expanded.len() - output_start, copied);
+                offsets.push(expanded.len() - output_start + (start - copied), start);
+            }
+            expanded.push_str(&jsonc[copied..start]);
+            push_escaped(expanded, &value);
+            copied = end;
+            substituted = true;
+        }
+    }
+
+    if !substituted {
+        return Ok(false);
+    }
+
+    if let Some(offsets) = offsets {
+        offsets.push(expanded.len() - output_start, copied);
+    }
+    expanded.push_str(&jsonc[copied..]);
+    Ok(true)
+}
+
+/// Appends `value` to `s`, escaped such that it is valid
+/// inside a [JSON] string.
+///
+/// [JSON]: https://www.json.org/json-en.html
+pub(crate) fn push_escaped(s: &mut String, value: &str) {
+    for c in value.chars() {
+        match c {
+            '"' => s.push_str("\\\""),
+            '\\' => s.push_str("\\\\"),
+            '\n' => s.push_str("\\n"),
+            '\r' => s.push_str("\\r"),
+            '\t' => s.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                write!(s, "\\u{:04x}", c as u32).unwrap();
+            }
+            c => s.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_vars() {
+        let vars = HashMap::from([("A", "a"), ("B", "\\\n\u{1}"), ("", "empty")]);
+
+        let expand = |jsonc| jsonc_expand_vars(jsonc, &vars);
+        assert_eq!(expand(r#""${A}""#), r#""a""#);
+        assert_eq!(expand(r#""${A}${A}-${B}""#), r#""aa-\\\n\u0001""#);
+        assert_eq!(expand(r#""${}" "${A""#), r#""empty" "${A""#);
+        assert_eq!(expand(r#""$A" "${{A}""#), r#""$A" "${{A}""#);
+        assert_eq!(
+            expand("[/* ${A} */ \"${A}\", ${A},] // ${A}"),
+            "[ \"a\", ${A}] "
+        );
+        assert_eq!(expand(r#""${C}" "${A}""#), r#""${C}" "a""#);
+    }
+
+    #[test]
+    fn test_from_path_with_env() {
+        let path = env::temp_dir().join(format!(
+            "jsonc_to_json_test_from_path_with_env.{}.jsonc",
+            std::process::id()
+        ));
+        fs::write(
+            &path,
+            "{\"path\": \"${JSONC_TO_JSON_TEST_VAR}/${JSONC_TO_JSON_UNSET}\", // ${JSONC_TO_JSON_TEST_VAR}\n}",
+        )
+        .unwrap();
+
+        env::set_var("JSONC_TO_JSON_TEST_VAR", "a\"b");
+
+        let json = jsonc_to_json_from_path_with_env(&path).unwrap();
+        assert_eq!(json, "{\"path\": \"a\\\"b/\" \n}");
+
+        let json = jsonc_to_json_from_path_with_env_or(&path, UnknownVar::Preserve).unwrap();
+        assert_eq!(json, "{\"path\": \"a\\\"b/${JSONC_TO_JSON_UNSET}\" \n}");
+
+        let err = jsonc_to_json_from_path_with_env_or(&path, UnknownVar::Error).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_substitute_vars_offsets() {
+        let vars = HashMap::from([("A", "a"), ("LONG", "long value")]);
+        let lookup = |name: &str| vars.get(name).map(|&value| Cow::Borrowed(value));
+
+        let jsonc = "[\"${LONG}\", \"${A}-\", 1]";
+        let mut expanded = String::new();
+        let mut offsets = OffsetMap::default();
+        let substituted = substitute_vars_into(
+            jsonc,
+            lookup,
+            UnknownVar::Error,
+            &mut expanded,
+            Some(&mut offsets),
+        );
+        assert_eq!(substituted, Ok(true));
+        assert_eq!(expanded, "[\"long value\", \"a-\", 1]");
+
+        // Offsets in the substituted values are clamped to the end of the reference
+        let mapped = [0, 1, 2, 5, 12, 13, 15, 16, 17, 18, 21, 23]
+            .map(|offset| offsets.to_input(offset))
+            .to_vec();
+        assert_eq!(mapped, [0, 1, 2, 5, 9, 10, 12, 13, 17, 18, 21, 23]);
+    }
+
+    #[test]
+    fn test_expand_vars_borrowed() {
+        let vars = HashMap::from([("A", "a")]);
+        let json = jsonc_expand_vars("[\"${B}\", 1]", &vars);
+        assert!(matches!(json, Cow::Borrowed("[\"${B}\", 1]")));
+    }
+}
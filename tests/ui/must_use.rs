@@ -0,0 +1,15 @@
+#![deny(unused_must_use)]
+
+use jsonc_to_json::{jsonc_to_json, jsonc_to_json_iter, JsonCConfig, JsonCToJsonIter};
+
+fn main() {
+    let jsonc = "[1, 2,]";
+
+    jsonc_to_json(jsonc);
+    jsonc_to_json_iter(jsonc);
+    JsonCToJsonIter::new(jsonc);
+    jsonc_to_json_iter(jsonc).with_offsets();
+
+    let config = JsonCConfig::new();
+    config.max_input_bytes(1024);
+}